@@ -0,0 +1,73 @@
+use approx::{AbsDiffEq, RelativeEq};
+use dec::Decimal128;
+
+use crate::{decimal, UncertainDecimal};
+
+/// Both value and uncertainty must be within tolerance. As with floats, NaN
+/// in either field compares unequal under every tolerance.
+impl AbsDiffEq for UncertainDecimal {
+    type Epsilon = Decimal128;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Decimal128::ZERO
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        abs_diff_eq(self.value, other.value, epsilon)
+            && abs_diff_eq(self.uncertainty, other.uncertainty, epsilon)
+    }
+}
+
+/// Both value and uncertainty must be within the relative tolerance. NaN in
+/// either field compares unequal under every tolerance.
+impl RelativeEq for UncertainDecimal {
+    fn default_max_relative() -> Self::Epsilon {
+        Decimal128::ZERO
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        relative_eq(self.value, other.value, epsilon, max_relative)
+            && relative_eq(self.uncertainty, other.uncertainty, epsilon, max_relative)
+    }
+}
+
+fn abs_diff_eq(a: Decimal128, b: Decimal128, epsilon: Decimal128) -> bool {
+    decimal::abs(a - b) <= epsilon
+}
+
+fn relative_eq(
+    a: Decimal128,
+    b: Decimal128,
+    epsilon: Decimal128,
+    max_relative: Decimal128,
+) -> bool {
+    if abs_diff_eq(a, b, epsilon) {
+        return true;
+    }
+
+    let (a, b) = (decimal::abs(a), decimal::abs(b));
+    let largest = if a > b { a } else { b };
+    decimal::abs(a - b) <= largest * max_relative
+}
+
+#[test]
+fn test_approx() {
+    use approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne};
+
+    let a = crate::ud!(1.000, 0.020);
+    let b = crate::ud!(1.001, 0.021);
+
+    assert_abs_diff_eq!(a, b, epsilon = crate::ud!(0.001).value);
+    assert_relative_eq!(a, b, max_relative = crate::ud!(0.05).value);
+    assert_relative_ne!(a, b);
+    assert_relative_ne!(
+        crate::ud!(NaN, 0.1),
+        crate::ud!(NaN, 0.1),
+        epsilon = crate::ud!(1).value
+    );
+}
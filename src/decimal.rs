@@ -2,6 +2,29 @@ use std::str::FromStr;
 
 use dec::{Context, Decimal, Decimal128, Rounding};
 
+/// π to the full 34 significant digits, 3.141592653589793238462643383279503.
+pub const PI: Decimal128 = Decimal128::from_ne_bytes(if cfg!(target_endian = "little") {
+    [
+        0x83, 0xe6, 0xb5, 0xda, 0xd0, 0x62, 0xe2, 0xb4, 0xfb, 0xb3, 0x53, 0xeb, 0x1a, 0xcc, 0xff, 0x2d,
+    ]
+} else {
+    [
+        0x2d, 0xff, 0xcc, 0x1a, 0xeb, 0x53, 0xb3, 0xfb, 0xb4, 0xe2, 0x62, 0xd0, 0xda, 0xb5, 0xe6, 0x83,
+    ]
+});
+
+/// Euler's number to the full 34 significant digits,
+/// 2.718281828459045235360287471352662.
+pub const E: Decimal128 = Decimal128::from_ne_bytes(if cfg!(target_endian = "little") {
+    [
+        0x62, 0x4b, 0x17, 0xe7, 0x5a, 0xe0, 0xd5, 0x54, 0x44, 0x96, 0x2e, 0x2d, 0x84, 0xf9, 0xff, 0x29,
+    ]
+} else {
+    [
+        0x29, 0xff, 0xf9, 0x84, 0x2d, 0x2e, 0x96, 0x44, 0x54, 0xd5, 0xe0, 0x5a, 0xe7, 0x17, 0x4b, 0x62,
+    ]
+});
+
 pub fn with_digits(mut dec: Decimal128, digits: u32) -> Decimal128 {
     let mut ctx = Context::<Decimal128>::default();
     ctx.set_rounding(Rounding::HalfUp);
@@ -13,7 +36,7 @@ pub fn with_digits(mut dec: Decimal128, digits: u32) -> Decimal128 {
 }
 
 pub fn with_min_digits(ctx: &mut Context<Decimal128>, mut dec: Decimal128, digits: u32) -> Decimal128 {
-    while dec.digits() < digits {
+    while dec.is_finite() && dec.digits() < digits {
         let exp = dec.exponent();
         ctx.rescale(&mut dec, exp - 1)
     }
@@ -22,14 +45,41 @@ pub fn with_min_digits(ctx: &mut Context<Decimal128>, mut dec: Decimal128, digit
 }
 
 pub fn with_max_digits(ctx: &mut Context<Decimal128>, mut dec: Decimal128, digits: u32) -> Decimal128 {
-    while dec.digits() > digits {
-        let exp = dec.exponent();
-        ctx.rescale(&mut dec, exp + 1)
+    // Every decimal has at least one digit, so a limit of 0 is treated as 1.
+    let digits = digits.max(1);
+    while dec.is_finite() && dec.digits() > digits {
+        // `rescale` truncates, so round to the context's mode by quantizing.
+        let mut place = Decimal128::ONE;
+        ctx.set_exponent(&mut place, dec.exponent() + (dec.digits() - digits) as i32);
+        dec = ctx.quantize(dec, place);
     }
 
     dec
 }
 
+pub fn with_exponent(dec: Decimal128, exponent: i32) -> Decimal128 {
+    if !dec.is_finite() {
+        return dec;
+    }
+
+    let mut ctx = Context::<Decimal128>::default();
+    ctx.set_rounding(Rounding::HalfUp);
+
+    let mut place = Decimal128::ONE;
+    ctx.set_exponent(&mut place, exponent);
+    ctx.quantize(dec, place)
+}
+
+pub fn abs(dec: Decimal128) -> Decimal128 {
+    Context::<Decimal128>::default().abs(dec)
+}
+
+/// Whether `dec` has an integral value, whatever its exponent: unlike
+/// [`Decimal128::is_integer`], this holds for `2.0`.
+pub fn is_integral(dec: Decimal128) -> bool {
+    dec.is_finite() && Context::<Decimal128>::default().round(dec) == dec
+}
+
 pub fn sqrt(dec: Decimal128) -> Decimal128 {
     let mut ctx = Context::<Decimal<12>>::default();
     ctx.set_rounding(Rounding::HalfUp);
@@ -38,3 +88,67 @@ pub fn sqrt(dec: Decimal128) -> Decimal128 {
     ctx.sqrt::<12>(&mut dec);
     Decimal128::from_str(&dec.to_string()).unwrap()
 }
+
+fn math_context() -> Context<Decimal<12>> {
+    // decNumber's transcendental functions reject contexts whose exponent
+    // range exceeds 999999
+    let mut ctx = Context::<Decimal<12>>::default();
+    ctx.set_rounding(Rounding::HalfUp);
+    ctx.set_max_exponent(999_999).unwrap();
+    ctx.set_min_exponent(-999_999).unwrap();
+
+    ctx
+}
+
+pub fn pow(dec: Decimal128, exp: Decimal128) -> Decimal128 {
+    let mut ctx = math_context();
+
+    let mut dec: Decimal<12> = dec.into();
+    let exp: Decimal<12> = exp.into();
+    ctx.pow(&mut dec, &exp);
+    Decimal128::from_str(&dec.to_string()).unwrap()
+}
+
+pub fn ln(dec: Decimal128) -> Decimal128 {
+    let mut ctx = math_context();
+
+    let mut dec: Decimal<12> = dec.into();
+    ctx.ln(&mut dec);
+    Decimal128::from_str(&dec.to_string()).unwrap()
+}
+
+pub fn exp(dec: Decimal128) -> Decimal128 {
+    let mut ctx = math_context();
+
+    let mut dec: Decimal<12> = dec.into();
+    ctx.exp(&mut dec);
+    Decimal128::from_str(&dec.to_string()).unwrap()
+}
+
+pub fn infinity() -> Decimal128 {
+    Decimal128::from_str("Infinity").unwrap()
+}
+
+pub fn max() -> Decimal128 {
+    Decimal128::from_str("9.999999999999999999999999999999999E+6144").unwrap()
+}
+
+/// Whether `a` and `b` differ by at most `ulps` units in the 34th significant
+/// digit of the larger magnitude, for comparing results that went through
+/// lossy conversions such as [`sqrt`]'s.
+pub fn approx_eq(a: Decimal128, b: Decimal128, ulps: u32) -> bool {
+    if a == b {
+        return true;
+    }
+    if !a.is_finite() || !b.is_finite() {
+        return false;
+    }
+
+    let mut ctx = Context::<Decimal128>::default();
+    let (a_abs, b_abs) = (ctx.abs(a), ctx.abs(b));
+    let larger = if a_abs > b_abs { a_abs } else { b_abs };
+
+    let mut tolerance = Decimal128::from(ulps);
+    ctx.set_exponent(&mut tolerance, larger.exponent() + larger.digits() as i32 - 34);
+    ctx.abs(a - b) <= tolerance
+}
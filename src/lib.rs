@@ -1,21 +1,46 @@
 use std::{
-    cmp::min,
-    fmt::Display,
+    cell::RefCell,
+    cmp::{max, min},
+    error::Error,
+    fmt::{Debug, Display},
     iter::{Product, Sum},
-    ops::{Add, Div, Mul, Neg, Sub},
+    ops::{Add, Div, Mul, Neg, RangeInclusive, Sub},
     str::FromStr,
 };
 
-use dec::{Context, Decimal128, Rounding};
+use dec::{Context, Decimal, Decimal128, Rounding};
 
-mod decimal;
+pub use dec;
 
-#[derive(Clone, Copy, Debug, Default)]
+#[cfg(feature = "approx")]
+mod approx_impls;
+pub mod decimal;
+mod running_stats;
+mod systematic;
+
+pub use running_stats::RunningStats;
+pub use systematic::UncertainDecimalWithSystematic;
+
+/// A value with its standard (1σ) uncertainty.
+#[derive(Clone, Copy, Default, PartialEq)]
 pub struct UncertainDecimal {
     pub value: Decimal128,
     pub uncertainty: Decimal128,
 }
 
+impl Debug for UncertainDecimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UncertainDecimal")
+            .field("value", &self.value)
+            .field("value_exponent", &self.value.exponent())
+            .field("value_digits", &self.value.digits())
+            .field("uncertainty", &self.uncertainty)
+            .field("uncertainty_exponent", &self.uncertainty.exponent())
+            .field("uncertainty_digits", &self.uncertainty.digits())
+            .finish()
+    }
+}
+
 impl Display for UncertainDecimal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} ± {}", self.value, self.uncertainty)
@@ -23,190 +48,3478 @@ impl Display for UncertainDecimal {
 }
 
 impl UncertainDecimal {
-    pub fn canonical(mut self) -> Self {
-        let mut ctx = Context::<Decimal128>::default();
-        ctx.set_rounding(Rounding::HalfUp);
-        self.uncertainty = decimal::with_max_digits(&mut ctx, self.uncertainty.canonical(), 1);
+    /// Exactly [`decimal::PI`], to 34 significant digits.
+    pub const PI: UncertainDecimal = UncertainDecimal {
+        value: decimal::PI,
+        uncertainty: Decimal128::ZERO,
+    };
+
+    /// Exactly [`decimal::E`], to 34 significant digits.
+    pub const E: UncertainDecimal = UncertainDecimal {
+        value: decimal::E,
+        uncertainty: Decimal128::ZERO,
+    };
+
+    /// NaN in both fields, the result of undefined operations.
+    pub const NAN: UncertainDecimal = UncertainDecimal {
+        value: Decimal128::NAN,
+        uncertainty: Decimal128::NAN,
+    };
+
+    pub fn exact(value: Decimal128) -> Self {
+        UncertainDecimal {
+            value,
+            uncertainty: Decimal128::ZERO,
+        }
+    }
+
+    pub fn from_relative(value: Decimal128, relative_uncertainty: Decimal128) -> Self {
+        UncertainDecimal {
+            value,
+            uncertainty: decimal::abs(value * relative_uncertainty),
+        }
+    }
+
+    /// Builds a measurement whose uncertainty is `ulps` units in the last
+    /// decimal place of `value`, as in "±2 counts".
+    pub fn from_value_ulps(value: Decimal128, ulps: u32) -> Self {
+        let mut uncertainty = Decimal128::from(ulps);
+        Context::<Decimal128>::default().set_exponent(&mut uncertainty, value.exponent());
+
+        UncertainDecimal { value, uncertainty }.canonical()
+    }
+
+    /// How many units in the value's last decimal place the uncertainty
+    /// spans; the inverse of [`Self::from_value_ulps`].
+    pub fn uncertainty_ulps(&self) -> Decimal128 {
+        let mut ulp = Decimal128::ONE;
+        Context::<Decimal128>::default().set_exponent(&mut ulp, self.value.exponent());
+
+        decimal::abs(self.uncertainty) / ulp
+    }
+
+    /// Builds a measurement from an interval of `±half_width` quoted with
+    /// coverage factor `k`, e.g. `k = 2` for about 95% confidence.
+    ///
+    /// The stored uncertainty is always 1σ, so the factor is divided out:
+    /// `uncertainty = half_width / k`.
+    pub fn from_confidence_interval(
+        value: Decimal128,
+        half_width: Decimal128,
+        k: Decimal128,
+    ) -> Self {
+        UncertainDecimal {
+            value,
+            uncertainty: decimal::abs(half_width / k),
+        }
+    }
+
+    pub fn from_bounds(lower: Decimal128, upper: Decimal128) -> Self {
+        let two = Decimal128::from(2);
+
+        UncertainDecimal {
+            value: (lower + upper) / two,
+            uncertainty: decimal::abs(upper - lower) / two,
+        }
+    }
+
+    pub fn lower_bound(&self) -> Decimal128 {
+        self.value - self.uncertainty
+    }
+
+    pub fn upper_bound(&self) -> Decimal128 {
+        self.value + self.uncertainty
+    }
+
+    /// Whether `x` lies within `[value − uncertainty, value + uncertainty]`.
+    pub fn contains(&self, x: Decimal128) -> bool {
+        self.lower_bound() <= x && x <= self.upper_bound()
+    }
+
+    /// Whether `x` lies within `k` standard uncertainties of the value.
+    pub fn contains_sigma(&self, x: Decimal128, k: Decimal128) -> bool {
+        decimal::abs(x - self.value) <= k * self.uncertainty
+    }
+
+    /// The distance between the two values in units of their combined
+    /// standard uncertainty, `|a − b| / √(u_a² + u_b²)`.
+    ///
+    /// Equal exact values are zero sigmas apart and unequal ones infinitely
+    /// many.
+    pub fn sigma_difference(&self, other: &UncertainDecimal) -> Decimal128 {
+        let difference = decimal::abs(self.value - other.value);
+        if difference.is_zero() {
+            return Decimal128::ZERO;
+        }
+
+        difference
+            / decimal::sqrt(
+                self.uncertainty * self.uncertainty + other.uncertainty * other.uncertainty,
+            )
+    }
+
+    /// Whether the two measurements lie within `sigma` combined standard
+    /// uncertainties of each other; see [`Self::sigma_difference`].
+    pub fn agrees_with(&self, other: &UncertainDecimal, sigma: Decimal128) -> bool {
+        self.sigma_difference(other) <= sigma
+    }
+
+    /// Stopping criterion for iterations: whether this iterate agrees with
+    /// `previous` within `tol_sigma` combined standard uncertainties.
+    pub fn converged(&self, previous: &UncertainDecimal, tol_sigma: Decimal128) -> bool {
+        self.agrees_with(previous, tol_sigma)
+    }
+
+    /// The pull `(value − reference)/uncertainty`, which should be
+    /// standard-normal across many measurements if the errors are well
+    /// estimated.
+    ///
+    /// An exact measurement has a signed infinite pull, or NaN if it equals
+    /// `reference`.
+    pub fn pull(&self, reference: Decimal128) -> Decimal128 {
+        (self.value - reference) / decimal::abs(self.uncertainty)
+    }
+
+    /// Whether the value is less than `x`, ignoring the uncertainty.
+    pub fn lt_value(&self, x: Decimal128) -> bool {
+        self.value < x
+    }
+
+    /// Whether the value is greater than `x`, ignoring the uncertainty.
+    pub fn gt_value(&self, x: Decimal128) -> bool {
+        self.value > x
+    }
+
+    /// Whether the value equals `x` numerically, ignoring the uncertainty and
+    /// trailing zeros.
+    pub fn eq_value(&self, x: Decimal128) -> bool {
+        self.value == x
+    }
+
+    /// Whether the value exceeds `threshold` by more than `sigma` standard
+    /// uncertainties, as for a detection decision.
+    pub fn is_significant(&self, threshold: Decimal128, sigma: Decimal128) -> bool {
+        self.value - threshold > sigma * self.uncertainty
+    }
+
+    pub fn relative_uncertainty(&self) -> Decimal128 {
+        decimal::abs(self.uncertainty) / decimal::abs(self.value)
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.uncertainty.is_zero()
+    }
+
+    /// One unit in the last place of the canonical value: the smallest change
+    /// in the value representable at its canonical precision.
+    ///
+    /// An uncertainty below this means the value is stored too coarsely.
+    pub fn uncertainty_resolution(&self) -> Decimal128 {
+        let canonical = self.canonical();
+        if !canonical.value.is_finite() {
+            return Decimal128::NAN;
+        }
+
+        let mut ulp = Decimal128::ONE;
+        Context::<Decimal128>::default().set_exponent(&mut ulp, canonical.value.exponent());
+        ulp
+    }
+
+    /// The bare value if the uncertainty is negligible, i.e. below
+    /// `tolerance` (or zero), otherwise `None`.
+    pub fn try_into_exact(&self, tolerance: Decimal128) -> Option<Decimal128> {
+        if self.is_exact() || decimal::abs(self.uncertainty) < tolerance {
+            Some(self.value)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_tuple(&self) -> (Decimal128, Decimal128) {
+        (self.value, self.uncertainty)
+    }
+
+    /// Rounds the uncertainty to one significant figure and the value to the
+    /// same place. Exact values are returned unrounded, with a plain zero
+    /// uncertainty.
+    pub fn canonical(self) -> Self {
+        self.canonical_with_figures(1)
+    }
+
+    /// Like [`Self::canonical`], but also reports whether rounding the value
+    /// to the uncertainty's place discarded any nonzero digits.
+    pub fn checked_canonical(self) -> (Self, bool) {
+        let canonical = self.canonical();
+        let lost = self.value.is_finite() && canonical.value != self.value;
+
+        (canonical, lost)
+    }
+
+    /// Canonicalizes both measurements at the decimal place of the larger
+    /// uncertainty, so they tabulate at matching precision.
+    ///
+    /// The finer measurement's uncertainty is rounded to that place, never
+    /// below one unit there. If both are exact they are returned canonical but
+    /// otherwise unchanged.
+    pub fn align(self, other: Self) -> (Self, Self) {
+        let (a, b) = (self.canonical(), other.canonical());
+        let exponent = [a, b]
+            .iter()
+            .filter(|m| !m.is_exact() && m.uncertainty.is_finite())
+            .map(|m| m.uncertainty.exponent())
+            .max();
+
+        match exponent {
+            Some(exponent) => (a.realigned(exponent), b.realigned(exponent)),
+            None => (a, b),
+        }
+    }
+
+    fn realigned(mut self, exponent: i32) -> Self {
+        self.value = decimal::with_exponent(self.value, exponent);
+        self.canonical()
+    }
+
+    pub fn canonical_with_figures(self, figures: u32) -> Self {
+        with_context(|ctx| self.canonical_in(ctx, figures))
+    }
+
+    fn canonical_in(mut self, ctx: &mut Context<Decimal128>, figures: u32) -> Self {
+        if !self.value.is_finite() || !self.uncertainty.is_finite() {
+            return self.propagate_non_finite();
+        }
+
+        // A zero uncertainty has no leading digit to round to. Rounding the
+        // value to the zero's exponent instead would turn `1.5 ± 0` into
+        // `2 ± 0` and strip the digits of every exact constant, so exact
+        // values keep their digits.
+        if self.is_exact() {
+            self.uncertainty = Decimal128::ZERO;
+            return self;
+        }
+
+        let uncertainty = ctx.abs(self.uncertainty);
+        self.uncertainty = decimal::with_max_digits(ctx, uncertainty, figures);
         if self.value.exponent() <= self.uncertainty.exponent() {
             self.value = ctx.quantize(self.value, self.uncertainty);
+            // A value rounded away entirely carries no sign information.
+            if self.value.is_zero() {
+                self.value = ctx.abs(self.value);
+            }
         } else {
-            self.uncertainty = Decimal128::ONE;
-            ctx.set_exponent(&mut self.uncertainty, self.value.exponent());
+            // The value has no digits at the uncertainty's place, so the
+            // uncertainty is rounded to the value's last place, but never
+            // below one unit there.
+            let mut ulp = Decimal128::ONE;
+            ctx.set_exponent(&mut ulp, self.value.exponent());
+            self.uncertainty = if self.uncertainty > ulp {
+                ctx.quantize(self.uncertainty, ulp)
+            } else {
+                ulp
+            };
         };
 
         self
     }
 
-    pub fn with_digits(mut self, digits: u32) -> UncertainDecimal {
-        self.value = decimal::with_digits(self.value, digits);
+    // Exact operands keep all their digits, so only measured ones limit the
+    // place a sum is rounded to; `None` if every operand is exact.
+    fn measured_place(operands: &[Self]) -> Option<i32> {
+        operands
+            .iter()
+            .filter(|m| !m.is_exact())
+            .map(|m| m.value.exponent())
+            .max()
+    }
 
-        self.canonical()
+    // Likewise for the significant digits of a product or quotient.
+    fn measured_digits(operands: &[Self]) -> Option<u32> {
+        operands
+            .iter()
+            .filter(|m| !m.is_exact())
+            .map(|m| m.value.digits())
+            .min()
     }
-}
 
-impl Add for UncertainDecimal {
-    type Output = UncertainDecimal;
+    // NaN in either field poisons both; otherwise an infinite value or
+    // uncertainty leaves the uncertainty infinite.
+    fn propagate_non_finite(mut self) -> Self {
+        if self.value.is_nan() || self.uncertainty.is_nan() {
+            self.value = Decimal128::NAN;
+            self.uncertainty = Decimal128::NAN;
+        } else if self.value.is_infinite() || self.uncertainty.is_infinite() {
+            self.uncertainty = decimal::infinity();
+        }
 
-    fn add(self, rhs: Self) -> Self::Output {
+        self
+    }
+
+    /// Adds without rounding the result; see [`fold`].
+    pub fn add_uncanonical(self, rhs: Self) -> Self {
         UncertainDecimal {
-            value: decimal::with_digits(
-                self.value + rhs.value,
-                min(self.value.digits(), rhs.value.digits()),
-            ),
-            uncertainty: decimal::sqrt(
-                self.uncertainty * self.uncertainty + rhs.uncertainty * rhs.uncertainty,
-            ),
+            value: self.value + rhs.value,
+            uncertainty: if self.is_exact() && rhs.is_exact() {
+                Decimal128::ZERO
+            } else {
+                decimal::sqrt(
+                    self.uncertainty * self.uncertainty + rhs.uncertainty * rhs.uncertainty,
+                )
+            },
         }
-        .canonical()
+        .propagate_non_finite()
     }
-}
 
-impl Div for UncertainDecimal {
-    type Output = UncertainDecimal;
+    /// Subtracts without rounding the result; see [`fold`].
+    pub fn sub_uncanonical(self, rhs: Self) -> Self {
+        self.add_uncanonical(-rhs)
+    }
 
-    fn div(self, rhs: Self) -> Self::Output {
+    /// Multiplies without rounding the result; see [`fold`].
+    pub fn mul_uncanonical(self, rhs: Self) -> Self {
         UncertainDecimal {
-            value: decimal::with_digits(
-                self.value / rhs.value,
-                min(self.value.digits(), rhs.value.digits()),
-            ),
-            uncertainty: decimal::sqrt(
-                self.uncertainty * self.uncertainty / self.value / self.value
-                    + rhs.uncertainty * rhs.uncertainty / rhs.value / rhs.value,
-            ) * self.value
-                / rhs.value,
+            value: self.value * rhs.value,
+            uncertainty: if self.is_exact() && rhs.is_exact() {
+                Decimal128::ZERO
+            } else {
+                decimal::sqrt(
+                    self.uncertainty * self.uncertainty / self.value / self.value
+                        + rhs.uncertainty * rhs.uncertainty / rhs.value / rhs.value,
+                ) * decimal::abs(self.value * rhs.value)
+            },
         }
-        .canonical()
+        .propagate_non_finite()
     }
-}
 
-impl Mul for UncertainDecimal {
-    type Output = UncertainDecimal;
+    /// Divides without rounding the result; see [`fold`].
+    pub fn div_uncanonical(self, rhs: Self) -> Self {
+        UncertainDecimal {
+            value: self.value / rhs.value,
+            uncertainty: if self.is_exact() && rhs.is_exact() {
+                Decimal128::ZERO
+            } else {
+                decimal::sqrt(
+                    self.uncertainty * self.uncertainty / self.value / self.value
+                        + rhs.uncertainty * rhs.uncertainty / rhs.value / rhs.value,
+                ) * decimal::abs(self.value / rhs.value)
+            },
+        }
+        .propagate_non_finite()
+    }
+
+    /// Evaluates `f(a, b)` for independent `a` and `b`, propagating their
+    /// uncertainties through the partial derivatives `dfa` and `dfb`.
+    pub fn propagate2(
+        a: UncertainDecimal,
+        b: UncertainDecimal,
+        f: impl Fn(Decimal128, Decimal128) -> Decimal128,
+        dfa: impl Fn(Decimal128, Decimal128) -> Decimal128,
+        dfb: impl Fn(Decimal128, Decimal128) -> Decimal128,
+    ) -> UncertainDecimal {
+        UncertainDecimal::propagate2_with_covariance(a, b, f, dfa, dfb, Decimal128::ZERO)
+    }
+
+    /// Like [`Self::propagate2`], adding the cross term for covariance `cov`
+    /// between `a` and `b`.
+    pub fn propagate2_with_covariance(
+        a: UncertainDecimal,
+        b: UncertainDecimal,
+        f: impl Fn(Decimal128, Decimal128) -> Decimal128,
+        dfa: impl Fn(Decimal128, Decimal128) -> Decimal128,
+        dfb: impl Fn(Decimal128, Decimal128) -> Decimal128,
+        cov: Decimal128,
+    ) -> UncertainDecimal {
+        let (dfa, dfb) = (dfa(a.value, b.value), dfb(a.value, b.value));
+        let (from_a, from_b) = (dfa * a.uncertainty, dfb * b.uncertainty);
 
-    fn mul(self, rhs: Self) -> Self::Output {
         UncertainDecimal {
-            value: decimal::with_digits(
-                self.value * rhs.value,
-                min(self.value.digits(), rhs.value.digits()),
-            ),
+            value: f(a.value, b.value),
             uncertainty: decimal::sqrt(
-                self.uncertainty * self.uncertainty / self.value / self.value
-                    + rhs.uncertainty * rhs.uncertainty / rhs.value / rhs.value,
-            ) * self.value
-                * rhs.value,
+                from_a * from_a + from_b * from_b + Decimal128::from(2) * dfa * dfb * cov,
+            ),
         }
         .canonical()
     }
-}
 
-impl Neg for UncertainDecimal {
-    type Output = Self;
+    /// Adds, clamping a value that overflows from finite operands to the
+    /// largest finite decimal of the same sign and marking the result with an
+    /// infinite uncertainty. Infinite operands give infinite results as for
+    /// `+`.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        let sum = self.add_uncanonical(rhs);
 
-    fn neg(self) -> Self::Output {
-        Self {
-            value: -self.value,
-            uncertainty: self.uncertainty,
+        if sum.value.is_infinite() && self.value.is_finite() && rhs.value.is_finite() {
+            sum.saturated()
+        } else {
+            self + rhs
         }
     }
-}
 
-impl Sub for UncertainDecimal {
-    type Output = UncertainDecimal;
+    /// Multiplies, saturating on overflow like [`Self::saturating_add`].
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        let product = self.mul_uncanonical(rhs);
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        self + (-rhs)
+        if product.value.is_infinite() && self.value.is_finite() && rhs.value.is_finite() {
+            product.saturated()
+        } else {
+            self * rhs
+        }
     }
-}
 
-impl Product for UncertainDecimal {
-    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
-        let mut prod_v = Decimal128::ONE;
-        let mut sum_sq_u = Decimal128::ONE;
+    /// Truncated subtraction `max(0, self − rhs)` for quantities that cannot
+    /// go negative.
+    ///
+    /// The uncertainties combine in quadrature as for `-`. The lower error bar
+    /// is clamped at zero, but as only one bar is stored, the result keeps
+    /// the larger upper bar, like [`Self::clamp_nonnegative_asymmetric`]; its
+    /// lower bound should be read as zero.
+    pub fn monus(self, rhs: Self) -> Self {
+        let mut difference = self - rhs;
+        if !difference.value.is_finite() || !difference.uncertainty.is_finite() {
+            return difference;
+        }
 
-        for UncertainDecimal { value, uncertainty } in iter {
-            prod_v *= value;
-            sum_sq_u += uncertainty * uncertainty / value / value;
+        if difference.value.is_negative() {
+            difference.value =
+                decimal::with_exponent(Decimal128::ZERO, difference.value.exponent());
         }
 
+        difference.canonical()
+    }
+
+    fn saturated(self) -> Self {
         UncertainDecimal {
-            value: prod_v,
-            uncertainty: decimal::sqrt(sum_sq_u) * prod_v,
+            value: if self.value.is_negative() {
+                -decimal::max()
+            } else {
+                decimal::max()
+            },
+            uncertainty: decimal::infinity(),
         }
-        .canonical()
     }
-}
 
-impl Sum for UncertainDecimal {
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        let mut sum_v = Decimal128::ZERO;
-        let mut sum_sq_u = Decimal128::ZERO;
+    /// Counts the leading significant digits on which the two values agree,
+    /// aligned by decimal place. Values of different sign or magnitude, zeros
+    /// and non-finite values agree on no digits.
+    pub fn matching_digits(&self, other: &UncertainDecimal) -> u32 {
+        let (a, b) = (self.value, other.value);
+        if !a.is_finite() || !b.is_finite() || a.is_zero() || b.is_zero() {
+            return 0;
+        }
+        if a.is_negative() != b.is_negative() {
+            return 0;
+        }
 
-        for UncertainDecimal { value, uncertainty } in iter {
-            sum_v += value;
-            sum_sq_u += uncertainty * uncertainty;
+        let leading = |d: Decimal128| d.exponent() + d.digits() as i32;
+        if leading(a) != leading(b) {
+            return 0;
         }
 
-        UncertainDecimal {
-            value: sum_v,
-            uncertainty: decimal::sqrt(sum_sq_u),
+        let (a, b) = (
+            a.coefficient().unsigned_abs().to_string(),
+            b.coefficient().unsigned_abs().to_string(),
+        );
+        a.bytes().zip(b.bytes()).take_while(|(a, b)| a == b).count() as u32
+    }
+
+    pub fn to_value_string(&self) -> String {
+        self.canonical().value.to_string()
+    }
+
+    /// The canonical value alone, with exactly the digits the uncertainty
+    /// justifies, for use in prose.
+    ///
+    /// Unlike [`Self::to_value_string`], small values are written out in full
+    /// (`0.00000123`), since leading zeros are not significant. Values rounded
+    /// to tens or more stay in scientific notation (`1.2E+3`) because trailing
+    /// zeros would claim extra figures.
+    pub fn value_to_significant_string(&self) -> String {
+        let value = self.canonical().value;
+        if value.is_finite() && value.exponent() <= 0 {
+            value.to_standard_notation_string()
+        } else {
+            value.to_string()
         }
-        .canonical()
     }
-}
 
-impl FromStr for UncertainDecimal {
-    type Err = ();
+    pub fn to_uncertainty_string(&self) -> String {
+        self.canonical().uncertainty.to_string()
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (l, r) = s.split_once("±").ok_or(())?;
+    /// Like [`Display`], but prints only the value when it is exact, as in
+    /// `42` rather than `42 ± 0`.
+    pub fn to_string_compact(&self) -> String {
+        if self.is_exact() {
+            self.value.to_string()
+        } else {
+            self.to_string()
+        }
+    }
 
-        Ok(Self {
-            value: Decimal128::from_str(l.trim()).map_err(|_| ())?,
-            uncertainty: Decimal128::from_str(r.trim()).map_err(|_| ())?,
-        })
+    /// Formats the canonical form in engineering notation, with value and
+    /// uncertainty sharing an exponent that is a multiple of 3, as in
+    /// `12.3e3 ± 0.5e3`. The exponent is omitted when it is zero.
+    pub fn to_engineering_string(&self) -> String {
+        match self.engineering_parts() {
+            Some((value, uncertainty, 0)) => {
+                format!("{} ± {}", value, uncertainty.as_deref().unwrap_or("0"))
+            }
+            Some((value, Some(uncertainty), exponent)) => {
+                format!("{}e{} ± {}e{}", value, exponent, uncertainty, exponent)
+            }
+            Some((value, None, exponent)) => format!("{}e{} ± 0", value, exponent),
+            None => self.canonical().to_string(),
+        }
     }
-}
 
-pub fn average(decs: &[Decimal128]) -> UncertainDecimal {
-    let len = Decimal128::from(decs.len() as u64);
-    let avg: Decimal128 = decs.iter().sum::<Decimal128>() / len;
+    /// Like [`Self::to_engineering_string`], but with the exponent written as
+    /// an SI prefix, as in `12.3k ± 0.5k`. Exponents beyond the prefixes
+    /// (`y` to `Y`) fall back to engineering notation.
+    pub fn to_si_string(&self) -> String {
+        const PREFIXES: [&str; 17] = [
+            "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+        ];
 
-    let std_dev = decimal::sqrt(
-        decs.iter()
-            .map(|d| {
-                let diff = *d - avg;
-                diff * diff
-            })
-            .sum::<Decimal128>()
-            / (len - Decimal128::ONE),
-    );
+        match self.engineering_parts() {
+            Some((value, uncertainty, exponent)) if (-24..=24).contains(&exponent) => {
+                let prefix = PREFIXES[(exponent / 3 + 8) as usize];
+                match uncertainty {
+                    Some(uncertainty) => format!("{}{} ± {}{}", value, prefix, uncertainty, prefix),
+                    None => format!("{}{} ± 0", value, prefix),
+                }
+            }
+            _ => self.to_engineering_string(),
+        }
+    }
 
-    UncertainDecimal {
-        value: avg,
-        uncertainty: std_dev,
+    // The canonical value and uncertainty in plain notation, scaled by the
+    // power of 1000 that leaves one to three integer digits in the value.
+    // Exact measurements have no uncertainty part, since scaling their zero
+    // would pad it with the value's digits.
+    fn engineering_parts(&self) -> Option<(String, Option<String>, i32)> {
+        let canonical = self.canonical();
+        if !canonical.value.is_finite() || !canonical.uncertainty.is_finite() {
+            return None;
+        }
+
+        let leading = if canonical.value.is_zero() {
+            &canonical.uncertainty
+        } else {
+            &canonical.value
+        };
+        let adjusted = leading.exponent() + leading.digits() as i32 - 1;
+        let exponent = if leading.is_zero() {
+            0
+        } else {
+            adjusted.div_euclid(3) * 3
+        };
+
+        let mut ctx = Context::<Decimal128>::default();
+        let shift = Decimal128::from(-exponent);
+        Some((
+            ctx.scaleb(canonical.value, shift)
+                .to_standard_notation_string(),
+            (!canonical.is_exact()).then(|| {
+                ctx.scaleb(canonical.uncertainty, shift)
+                    .to_standard_notation_string()
+            }),
+            exponent,
+        ))
     }
-}
 
-#[macro_export]
-macro_rules! ud {
-    ($v:expr, $u:expr) => {
-        (UncertainDecimal {
-            value: Decimal128::from_str(stringify!($v)).unwrap(),
-            uncertainty: Decimal128::from_str(stringify!($u)).unwrap(),
-        })
-    };
-}
+    /// Formats just the relative uncertainty as a percentage, like `±0.4%`.
+    ///
+    /// Rounds to one significant figure, but never past a whole percent. A
+    /// zero value gives `±∞%`.
+    pub fn to_relative_string(&self) -> String {
+        if self.is_exact() {
+            return "±0%".to_string();
+        }
+        if self.value.is_zero() {
+            return "±∞%".to_string();
+        }
 
-#[test]
-fn test() {
-    let a = ud!(1.7775, 0.6);
+        let percent = self.relative_uncertainty() * Decimal128::from(100);
+        if !percent.is_finite() {
+            return format!("±{}%", percent);
+        }
+        let leading = percent.exponent() + percent.digits() as i32 - 1;
+        format!("±{}%", decimal::with_exponent(percent, min(leading, 0)))
+    }
 
-    println!("{}", a.canonical());
+    /// A labeled multi-line summary of the canonical form: value, absolute
+    /// and relative uncertainty, and the 1σ interval.
+    pub fn to_report_string(&self) -> String {
+        let canonical = self.canonical();
 
-    let b = ud!(2000, 0.3).canonical();
+        format!(
+            "value:       {}\nuncertainty: {}\nrelative:    {}\ninterval:    [{}, {}]",
+            canonical.value,
+            canonical.uncertainty,
+            canonical.to_relative_string(),
+            canonical.lower_bound(),
+            canonical.upper_bound(),
+        )
+    }
 
-    println!("{}", b);
-    println!("{}", a + b);
+    /// Formats the canonical form followed by `unit`. In scientific notation
+    /// the exponent is shared and the unit follows it, as in
+    /// `(6.67 ± 0.01)E-11 m³/(kg·s²)`.
+    pub fn format_with_units(&self, unit: &str) -> String {
+        let canonical = self.canonical();
+        let (value, uncertainty) = (
+            canonical.to_value_string(),
+            canonical.to_uncertainty_string(),
+        );
 
-    println!("{}", b.with_digits(8))
+        let formatted = if value.contains('E') || uncertainty.contains('E') {
+            let leading = if canonical.value.is_zero() {
+                canonical.uncertainty
+            } else {
+                canonical.value
+            };
+            let exponent = leading.exponent() + leading.digits() as i32 - 1;
+            let mut ctx = Context::<Decimal128>::default();
+            let shift = Decimal128::from(-exponent);
+
+            // Scaling a zero would pad it with the value's digits.
+            let uncertainty = if canonical.is_exact() {
+                Decimal128::ZERO
+            } else {
+                ctx.scaleb(canonical.uncertainty, shift)
+            };
+
+            format!(
+                "({} ± {})E{:+}",
+                ctx.scaleb(canonical.value, shift),
+                uncertainty,
+                exponent
+            )
+        } else {
+            format!("{} ± {}", value, uncertainty)
+        };
+
+        if unit.is_empty() {
+            formatted
+        } else {
+            format!("{} {}", formatted, unit)
+        }
+    }
+
+    pub fn powf_const(self, exp: Decimal128) -> UncertainDecimal {
+        if self.value.is_negative() && !decimal::is_integral(exp) {
+            return UncertainDecimal::NAN;
+        }
+
+        let value = decimal::pow(self.value, exp);
+
+        UncertainDecimal {
+            value: match UncertainDecimal::measured_digits(&[self]) {
+                Some(digits) => decimal::with_digits(value, digits),
+                None => value,
+            },
+            uncertainty: decimal::abs(exp * decimal::pow(self.value, exp - Decimal128::ONE))
+                * self.uncertainty,
+        }
+        .canonical()
+    }
+
+    /// Returns `10^self`, the inverse of a base-10 logarithm.
+    ///
+    /// Exact integer powers take the [`Self::pow10_exact`] fast path.
+    pub fn exp10(self) -> UncertainDecimal {
+        if self.is_exact() && decimal::is_integral(self.value) {
+            return UncertainDecimal::pow10_exact(self.value);
+        }
+
+        exact_base_pow(Decimal128::from(10), self)
+    }
+
+    /// Returns exactly `10^exp` by setting the decimal exponent, or NaN if
+    /// `exp` is not an integer or `10^exp` overflows or underflows
+    /// [`Decimal128`].
+    pub fn pow10_exact(exp: Decimal128) -> UncertainDecimal {
+        let mut ctx = Context::<Decimal<12>>::default();
+        let exponent = match ctx.try_into_i32(exp.into()) {
+            Ok(exponent) => exponent,
+            Err(_) => return UncertainDecimal::NAN,
+        };
+
+        let mut value = Decimal128::ONE;
+        Context::<Decimal128>::default().set_exponent(&mut value, exponent);
+        if !value.is_finite() || value.is_zero() {
+            return UncertainDecimal::NAN;
+        }
+
+        UncertainDecimal::exact(value)
+    }
+
+    /// Rounds the value down to an integer.
+    ///
+    /// Like [`Self::ceil`], [`Self::round`] and [`Self::trunc`], this keeps the
+    /// uncertainty unchanged rather than propagating the zero derivative, and
+    /// the result is not canonicalized, which would coarsen the uncertainty to
+    /// the integer place.
+    pub fn floor(self) -> UncertainDecimal {
+        self.to_integer(Rounding::Floor)
+    }
+
+    pub fn ceil(self) -> UncertainDecimal {
+        self.to_integer(Rounding::Ceiling)
+    }
+
+    /// Rounds the value to the nearest integer, with halves away from zero.
+    pub fn round(self) -> UncertainDecimal {
+        self.to_integer(Rounding::HalfUp)
+    }
+
+    pub fn trunc(self) -> UncertainDecimal {
+        self.to_integer(Rounding::Down)
+    }
+
+    fn to_integer(mut self, rounding: Rounding) -> UncertainDecimal {
+        let mut ctx = Context::<Decimal128>::default();
+        ctx.set_rounding(rounding);
+        self.value = ctx.quantize(self.value, Decimal128::ONE);
+
+        self
+    }
+
+    /// Returns `1/value²` with uncertainty `2·u/|value|³` in one step.
+    ///
+    /// A zero value gives an infinite value and uncertainty.
+    pub fn inv_square(self) -> UncertainDecimal {
+        if self.value.is_zero() {
+            return UncertainDecimal {
+                value: decimal::infinity(),
+                uncertainty: decimal::infinity(),
+            };
+        }
+
+        let square = self.value * self.value;
+        let value = Decimal128::ONE / square;
+
+        UncertainDecimal {
+            value: match UncertainDecimal::measured_digits(&[self]) {
+                Some(digits) => decimal::with_digits(value, digits),
+                None => value,
+            },
+            uncertainty: Decimal128::from(2) * self.uncertainty * value / decimal::abs(self.value),
+        }
+        .canonical()
+    }
+
+    /// Folds an absolute systematic uncertainty into the existing one in
+    /// quadrature, `√(u² + syst²)`.
+    ///
+    /// Use [`UncertainDecimalWithSystematic`] to keep the two apart instead.
+    pub fn add_systematic(mut self, syst: Decimal128) -> UncertainDecimal {
+        self.uncertainty = decimal::sqrt(self.uncertainty * self.uncertainty + syst * syst);
+
+        self.canonical()
+    }
+
+    /// Multiplies by the exact factor `k`, as for a unit conversion, leaving
+    /// the relative uncertainty unchanged.
+    ///
+    /// Unlike `*`, which also propagates the factor's uncertainty, nothing is
+    /// added in quadrature.
+    pub fn scale(self, k: Decimal128) -> UncertainDecimal {
+        UncertainDecimal {
+            value: self.value * k,
+            uncertainty: self.uncertainty * decimal::abs(k),
+        }
+        .canonical()
+    }
+
+    /// For a positive value whose lower error bar dips below zero, truncates
+    /// the lower bar so the interval stops at zero, returning the canonical
+    /// `(value, plus, minus)`.
+    ///
+    /// The value and upper bar are left intact. Since the result is
+    /// asymmetric it is not an [`UncertainDecimal`]; other measurements get
+    /// equal bars, and [`Self::monus`] covers results that may go negative.
+    pub fn clamp_nonnegative_asymmetric(self) -> (Decimal128, Decimal128, Decimal128) {
+        let canonical = self.canonical();
+        let minus = if canonical.value.is_positive() && canonical.lower_bound().is_negative() {
+            canonical.value
+        } else {
+            canonical.uncertainty
+        };
+
+        (canonical.value, canonical.uncertainty, minus)
+    }
+
+    pub fn clamp_uncertainty_minimum(mut self, floor: Decimal128) -> UncertainDecimal {
+        if self.uncertainty < floor {
+            self.uncertainty = floor;
+        }
+
+        self.canonical()
+    }
+
+    /// Raises the uncertainty to `|value|·min_rel` if the relative uncertainty
+    /// is below `min_rel`. A zero value keeps its uncertainty.
+    pub fn clamp_relative_minimum(mut self, min_rel: Decimal128) -> UncertainDecimal {
+        let floor = decimal::abs(self.value * min_rel);
+        if !self.value.is_zero() && decimal::abs(self.uncertainty) < floor {
+            self.uncertainty = floor;
+        }
+
+        self.canonical()
+    }
+
+    pub fn map_uncertainty<F: Fn(Decimal128) -> Decimal128>(mut self, f: F) -> UncertainDecimal {
+        self.uncertainty = f(self.uncertainty);
+
+        self.canonical()
+    }
+
+    pub fn with_digits(mut self, digits: u32) -> UncertainDecimal {
+        self.value = decimal::with_digits(self.value, digits);
+
+        self.canonical()
+    }
+
+    /// Stores both value and uncertainty with the decimal exponent `exp`, as
+    /// when a table column is expressed in units of `10^exp`.
+    ///
+    /// A finer exponent only pads with zeros. A coarser one rounds half up,
+    /// except that a nonzero uncertainty never rounds below one unit. The
+    /// result is not canonicalized.
+    pub fn with_exponent(mut self, exp: i32) -> UncertainDecimal {
+        let uncertainty = decimal::with_exponent(self.uncertainty, exp);
+        self.value = decimal::with_exponent(self.value, exp);
+        self.uncertainty = if uncertainty.is_zero() && !self.uncertainty.is_zero() {
+            let mut ulp = Decimal128::ONE;
+            Context::<Decimal128>::default().set_exponent(&mut ulp, exp);
+            ulp
+        } else {
+            uncertainty
+        };
+
+        self
+    }
+}
+
+impl Add for UncertainDecimal {
+    type Output = UncertainDecimal;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let sum = self.add_uncanonical(rhs);
+
+        UncertainDecimal {
+            value: match UncertainDecimal::measured_place(&[self, rhs]) {
+                Some(place) => decimal::with_exponent(sum.value, place),
+                None => sum.value,
+            },
+            ..sum
+        }
+        .canonical()
+    }
+}
+
+impl Div for UncertainDecimal {
+    type Output = UncertainDecimal;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let quotient = self.div_uncanonical(rhs);
+
+        UncertainDecimal {
+            value: match UncertainDecimal::measured_digits(&[self, rhs]) {
+                Some(digits) => decimal::with_digits(quotient.value, digits),
+                None => quotient.value,
+            },
+            ..quotient
+        }
+        .canonical()
+    }
+}
+
+impl Mul for UncertainDecimal {
+    type Output = UncertainDecimal;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let product = self.mul_uncanonical(rhs);
+
+        UncertainDecimal {
+            value: match UncertainDecimal::measured_digits(&[self, rhs]) {
+                Some(digits) => decimal::with_digits(product.value, digits),
+                None => product.value,
+            },
+            ..product
+        }
+        .canonical()
+    }
+}
+
+impl Neg for UncertainDecimal {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            value: -self.value,
+            uncertainty: self.uncertainty,
+        }
+    }
+}
+
+impl Sub for UncertainDecimal {
+    type Output = UncertainDecimal;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+macro_rules! forward_ref_binop {
+    ($imp:ident, $method:ident) => {
+        impl $imp<&UncertainDecimal> for UncertainDecimal {
+            type Output = UncertainDecimal;
+
+            fn $method(self, rhs: &UncertainDecimal) -> Self::Output {
+                $imp::$method(self, *rhs)
+            }
+        }
+
+        impl $imp<UncertainDecimal> for &UncertainDecimal {
+            type Output = UncertainDecimal;
+
+            fn $method(self, rhs: UncertainDecimal) -> Self::Output {
+                $imp::$method(*self, rhs)
+            }
+        }
+
+        impl $imp<&UncertainDecimal> for &UncertainDecimal {
+            type Output = UncertainDecimal;
+
+            fn $method(self, rhs: &UncertainDecimal) -> Self::Output {
+                $imp::$method(*self, *rhs)
+            }
+        }
+    };
+}
+
+forward_ref_binop!(Add, add);
+forward_ref_binop!(Div, div);
+forward_ref_binop!(Mul, mul);
+forward_ref_binop!(Sub, sub);
+
+/// The product of no factors is exactly `1 ± 0`.
+impl Product for UncertainDecimal {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let mut prod_v = Decimal128::ONE;
+        let mut sum_sq_u = Decimal128::ZERO;
+
+        for UncertainDecimal { value, uncertainty } in iter {
+            prod_v *= value;
+            if !uncertainty.is_zero() {
+                sum_sq_u += uncertainty * uncertainty / value / value;
+            }
+        }
+
+        UncertainDecimal {
+            value: prod_v,
+            uncertainty: decimal::sqrt(sum_sq_u) * decimal::abs(prod_v),
+        }
+        .canonical()
+    }
+}
+
+/// The sum of no terms is exactly `0 ± 0`.
+impl Sum for UncertainDecimal {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let mut sum_v = Decimal128::ZERO;
+        let mut sum_sq_u = Decimal128::ZERO;
+        let mut exponent = None;
+
+        for UncertainDecimal { value, uncertainty } in iter {
+            sum_v += value;
+            sum_sq_u += uncertainty * uncertainty;
+            // Exact summands keep their digits and do not set the place.
+            if !uncertainty.is_zero() {
+                exponent = max(exponent, Some(value.exponent()));
+            }
+        }
+
+        UncertainDecimal {
+            value: match exponent {
+                Some(exponent) => decimal::with_exponent(sum_v, exponent),
+                None => sum_v,
+            },
+            uncertainty: decimal::sqrt(sum_sq_u),
+        }
+        .canonical()
+    }
+}
+
+/// Collects readings into their [`average`], with the same NaN results for
+/// empty or single-reading input.
+impl FromIterator<Decimal128> for UncertainDecimal {
+    fn from_iter<I: IntoIterator<Item = Decimal128>>(iter: I) -> Self {
+        let readings: Vec<Decimal128> = iter.into_iter().collect();
+        average(&readings)
+    }
+}
+
+// Coefficient capacity of `Decimal128`.
+const MAX_DIGITS: usize = 34;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseUncertainDecimalError {
+    /// Neither `±` nor a parenthesized uncertainty was found.
+    MissingUncertainty,
+    InvalidValue,
+    InvalidUncertainty,
+    InvalidExponent,
+    /// A coefficient has more significant digits than [`Decimal128`] holds,
+    /// or a finite number is too large or too small for it.
+    Overflow,
+}
+
+impl Display for ParseUncertainDecimalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ParseUncertainDecimalError::MissingUncertainty => "missing uncertainty",
+            ParseUncertainDecimalError::InvalidValue => "invalid value",
+            ParseUncertainDecimalError::InvalidUncertainty => "invalid uncertainty",
+            ParseUncertainDecimalError::InvalidExponent => "invalid exponent",
+            ParseUncertainDecimalError::Overflow => "number out of range",
+        })
+    }
+}
+
+impl Error for ParseUncertainDecimalError {}
+
+impl UncertainDecimal {
+    /// Parses `value ± uncertainty`, concise notation like `6.674(15)e-11`, or
+    /// asymmetric uncertainties like `1.2 +0.3 -0.1`.
+    ///
+    /// The unicode minus `−` is read as `-`, and `+/-` or `+-` as `±`.
+    ///
+    /// Asymmetric uncertainties are symmetrized to the larger of the two; use
+    /// [`Self::parse_asymmetric`] to get both.
+    pub fn try_parse(s: &str) -> Result<Self, ParseUncertainDecimalError> {
+        let s = &normalize_glyphs(s);
+        let (l, r) = match s.split_once('±') {
+            Some(halves) => halves,
+            None if s.contains('(') => return parse_concise(s.trim()),
+            None => {
+                let (value, plus, minus) = UncertainDecimal::parse_asymmetric(s)?;
+                return Ok(UncertainDecimal {
+                    value,
+                    uncertainty: if plus > minus { plus } else { minus },
+                });
+            }
+        };
+
+        Ok(Self {
+            value: parse_decimal(l.trim(), ParseUncertainDecimalError::InvalidValue)?,
+            uncertainty: parse_decimal(r.trim(), ParseUncertainDecimalError::InvalidUncertainty)?,
+        })
+    }
+
+    /// Parses `value +plus -minus`, in either order, returning
+    /// `(value, plus, minus)` with both uncertainties non-negative.
+    pub fn parse_asymmetric(
+        s: &str,
+    ) -> Result<(Decimal128, Decimal128, Decimal128), ParseUncertainDecimalError> {
+        use ParseUncertainDecimalError::*;
+
+        let s = &normalize_glyphs(s);
+        let parse_uncertainty = |u: &str| match parse_decimal(u, InvalidUncertainty)? {
+            u if u.is_finite() && !u.is_negative() => Ok(u),
+            _ => Err(InvalidUncertainty),
+        };
+
+        let mut tokens = s.split_whitespace();
+        let value = tokens.next().ok_or(InvalidValue)?;
+        let value = parse_decimal(value, InvalidValue)?;
+
+        let (mut plus, mut minus) = (None, None);
+        for token in tokens {
+            let (slot, u) = if let Some(u) = token.strip_prefix('+') {
+                (&mut plus, u)
+            } else if let Some(u) = token.strip_prefix('-') {
+                (&mut minus, u)
+            } else {
+                return Err(InvalidUncertainty);
+            };
+            if slot.replace(parse_uncertainty(u)?).is_some() {
+                return Err(InvalidUncertainty);
+            }
+        }
+
+        match (plus, minus) {
+            (Some(plus), Some(minus)) => Ok((value, plus, minus)),
+            _ => Err(MissingUncertainty),
+        }
+    }
+}
+
+impl FromStr for UncertainDecimal {
+    type Err = ParseUncertainDecimalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        UncertainDecimal::try_parse(s)
+    }
+}
+
+// Accepts the unicode minus sign and ASCII spellings of `±` found in
+// published data.
+fn normalize_glyphs(s: &str) -> String {
+    s.replace('\u{2212}', "-")
+        .replace("+/-", "±")
+        .replace("+-", "±")
+}
+
+// Parses one decimal, rejecting coefficients too long to store exactly and
+// exponents out of range instead of letting them round or overflow.
+fn parse_decimal(
+    s: &str,
+    invalid: ParseUncertainDecimalError,
+) -> Result<Decimal128, ParseUncertainDecimalError> {
+    let mantissa = s.split(['e', 'E']).next().unwrap_or_default();
+    let digits = mantissa
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .skip_while(|&b| b == b'0')
+        .count();
+    if digits > MAX_DIGITS {
+        return Err(ParseUncertainDecimalError::Overflow);
+    }
+
+    let mut ctx = Context::<Decimal128>::default();
+    let dec = ctx.parse(s).map_err(|_| invalid)?;
+    if out_of_range(&ctx) {
+        return Err(ParseUncertainDecimalError::Overflow);
+    }
+
+    Ok(dec)
+}
+
+// Whether an operation on finite decimals overflowed, underflowed or, for an
+// exponent beyond any representable one, failed outright.
+fn out_of_range(ctx: &Context<Decimal128>) -> bool {
+    let status = ctx.status();
+    status.overflow() || status.underflow() || status.invalid_operation()
+}
+
+/// Parses concise notation like `6.674(15)e-11`, where the parenthesized
+/// digits count units in the mantissa's last place and the exponent applies to
+/// both value and uncertainty.
+fn parse_concise(s: &str) -> Result<UncertainDecimal, ParseUncertainDecimalError> {
+    use ParseUncertainDecimalError::*;
+
+    let (mantissa, rest) = s.split_once('(').ok_or(MissingUncertainty)?;
+    let (uncertainty, exponent) = rest.split_once(')').ok_or(InvalidUncertainty)?;
+
+    let mantissa = parse_decimal(mantissa.trim(), InvalidValue)?;
+    if !mantissa.is_finite() {
+        return Err(InvalidValue);
+    }
+    if uncertainty.is_empty() {
+        return Err(InvalidUncertainty);
+    }
+
+    let mut ctx = Context::<Decimal128>::default();
+    let mut uncertainty = if uncertainty.bytes().all(|b| b.is_ascii_digit()) {
+        let digits = parse_decimal(uncertainty, InvalidUncertainty)?;
+        ctx.scaleb(digits, Decimal128::from(mantissa.exponent()))
+    } else {
+        parse_decimal(uncertainty.trim(), InvalidUncertainty)?
+    };
+    if !uncertainty.is_finite() || uncertainty.is_negative() {
+        return Err(InvalidUncertainty);
+    }
+
+    let mut value = mantissa;
+    let exponent = exponent.trim();
+    if !exponent.is_empty() {
+        let exponent = exponent
+            .strip_prefix(['e', 'E'])
+            .and_then(|e| i32::from_str(e).ok())
+            .ok_or(InvalidExponent)?;
+        value = ctx.scaleb(value, Decimal128::from(exponent));
+        uncertainty = ctx.scaleb(uncertainty, Decimal128::from(exponent));
+    }
+    if out_of_range(&ctx) {
+        return Err(Overflow);
+    }
+
+    Ok(UncertainDecimal { value, uncertainty })
+}
+
+impl From<UncertainDecimal> for RangeInclusive<Decimal128> {
+    fn from(ud: UncertainDecimal) -> Self {
+        ud.lower_bound()..=ud.upper_bound()
+    }
+}
+
+impl From<RangeInclusive<Decimal128>> for UncertainDecimal {
+    fn from(range: RangeInclusive<Decimal128>) -> Self {
+        let (lower, upper) = range.into_inner();
+        UncertainDecimal::from_bounds(lower, upper)
+    }
+}
+
+impl From<(Decimal128, Decimal128)> for UncertainDecimal {
+    fn from((value, uncertainty): (Decimal128, Decimal128)) -> Self {
+        UncertainDecimal { value, uncertainty }.canonical()
+    }
+}
+
+pub fn canonical_all(decs: &mut [UncertainDecimal]) {
+    with_context(|ctx| {
+        for dec in decs {
+            *dec = dec.canonical_in(ctx, 1);
+        }
+    })
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Context<Decimal128>> = RefCell::new({
+        let mut ctx = Context::<Decimal128>::default();
+        ctx.set_rounding(Rounding::HalfUp);
+        ctx
+    });
+}
+
+// Runs `f` with this thread's cached `HalfUp` context, so canonicalizing
+// neither rebuilds a context each time nor contends across threads.
+fn with_context<R>(f: impl FnOnce(&mut Context<Decimal128>) -> R) -> R {
+    CONTEXT.with(|ctx| {
+        let mut ctx = ctx.borrow_mut();
+        ctx.clear_status();
+        f(&mut ctx)
+    })
+}
+
+/// Combined relative uncertainty of a product or ratio of `measurements`,
+/// without computing the product itself.
+///
+/// Zero-valued entries have no defined relative uncertainty and are skipped.
+pub fn total_relative_uncertainty(measurements: &[UncertainDecimal]) -> Decimal128 {
+    decimal::sqrt(
+        measurements
+            .iter()
+            .filter(|m| !m.value.is_zero())
+            .map(|m| {
+                let rel = m.relative_uncertainty();
+                rel * rel
+            })
+            .sum::<Decimal128>(),
+    )
+}
+
+/// How the contributions to an [`uncertainty_budget`] combine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BudgetMode {
+    /// A sum or difference: absolute uncertainties add in quadrature.
+    Additive,
+    /// A product or ratio: relative uncertainties add in quadrature.
+    Multiplicative,
+}
+
+/// Each named contribution's fractional share `u_i²/Σu²` of the total
+/// variance, in input order.
+///
+/// Shares are NaN if the total variance is zero.
+pub fn uncertainty_budget(
+    contributions: &[(&str, UncertainDecimal)],
+    mode: BudgetMode,
+) -> Vec<(String, Decimal128)> {
+    let variances: Vec<Decimal128> = contributions
+        .iter()
+        .map(|(_, m)| {
+            let u = match mode {
+                BudgetMode::Additive => m.uncertainty,
+                BudgetMode::Multiplicative => m.relative_uncertainty(),
+            };
+            u * u
+        })
+        .collect();
+    let total: Decimal128 = variances.iter().copied().sum();
+
+    contributions
+        .iter()
+        .zip(variances)
+        .map(|((name, _), variance)| (name.to_string(), variance / total))
+        .collect()
+}
+
+/// Combines `decs` left to right with `op`, canonicalizing only the result.
+///
+/// With the `*_uncanonical` operators this rounds once instead of at every
+/// step.
+pub fn reduce(
+    decs: &[UncertainDecimal],
+    op: impl Fn(UncertainDecimal, UncertainDecimal) -> UncertainDecimal,
+) -> Option<UncertainDecimal> {
+    Some(decs.iter().copied().reduce(op)?.canonical())
+}
+
+/// Splits `measurements` into those exceeding `threshold` by more than
+/// `sigma` standard uncertainties (see [`UncertainDecimal::is_significant`])
+/// and the rest, preserving input order within each.
+pub fn partition_significant(
+    measurements: &[UncertainDecimal],
+    threshold: Decimal128,
+    sigma: Decimal128,
+) -> (Vec<UncertainDecimal>, Vec<UncertainDecimal>) {
+    measurements
+        .iter()
+        .partition(|m| m.is_significant(threshold, sigma))
+}
+
+/// Sums the measurements falling in each bin between consecutive ascending
+/// `edges`, giving one total per bin.
+///
+/// Measurements are assigned by value alone, even when their uncertainty
+/// straddles an edge. Bins are half-open `[lo, hi)` except the last, which
+/// also includes its upper edge, and values outside all bins are dropped. An
+/// empty bin totals `0 ± 0`.
+pub fn histogram(measurements: &[UncertainDecimal], edges: &[Decimal128]) -> Vec<UncertainDecimal> {
+    let bins = edges.len().saturating_sub(1);
+
+    edges
+        .windows(2)
+        .enumerate()
+        .map(|(i, edge)| {
+            measurements
+                .iter()
+                .filter(|m| {
+                    edge[0] <= m.value && (m.value < edge[1] || i == bins - 1 && m.value == edge[1])
+                })
+                .copied()
+                .sum()
+        })
+        .collect()
+}
+
+/// How [`efficiency`] treats its numerator and denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EfficiencyMode {
+    /// Independent measurements, combined like `/`.
+    Independent,
+    /// Counts where `passed` is a subset of `total`: the uncertainty is the
+    /// binomial `√(p(1 − p)/n)`, ignoring the inputs' own uncertainties. It
+    /// vanishes at exactly 0% or 100%.
+    Binomial,
+}
+
+/// The percentage `passed/total · 100`, propagated according to `mode`.
+///
+/// Returns NaN if `total` is zero.
+pub fn efficiency(
+    passed: UncertainDecimal,
+    total: UncertainDecimal,
+    mode: EfficiencyMode,
+) -> UncertainDecimal {
+    if total.value.is_zero() {
+        return UncertainDecimal::NAN;
+    }
+
+    let hundred = Decimal128::from(100);
+    let ratio = match mode {
+        EfficiencyMode::Independent => passed.div_uncanonical(total),
+        EfficiencyMode::Binomial => {
+            let p = passed.value / total.value;
+            UncertainDecimal {
+                value: p,
+                uncertainty: decimal::sqrt(p * (Decimal128::ONE - p) / total.value),
+            }
+        }
+    };
+
+    UncertainDecimal {
+        value: ratio.value * hundred,
+        uncertainty: ratio.uncertainty * hundred,
+    }
+    .canonical()
+}
+
+/// The measurement furthest from `reference` in standard uncertainties (see
+/// [`UncertainDecimal::sigma_difference`]), e.g. the most significant signal
+/// when `reference` is zero.
+///
+/// Ties go to the earliest measurement, and any whose separation is NaN are
+/// skipped. Returns `None` if no measurement remains.
+pub fn max_by_significance(
+    measurements: &[UncertainDecimal],
+    reference: Decimal128,
+) -> Option<UncertainDecimal> {
+    by_significance(measurements, reference, |candidate, best| candidate > best)
+}
+
+/// The measurement most consistent with `reference`; the counterpart of
+/// [`max_by_significance`], with the same tie-breaking.
+pub fn min_by_significance(
+    measurements: &[UncertainDecimal],
+    reference: Decimal128,
+) -> Option<UncertainDecimal> {
+    by_significance(measurements, reference, |candidate, best| candidate < best)
+}
+
+fn by_significance(
+    measurements: &[UncertainDecimal],
+    reference: Decimal128,
+    better: impl Fn(Decimal128, Decimal128) -> bool,
+) -> Option<UncertainDecimal> {
+    let reference = UncertainDecimal::exact(reference);
+    let mut best: Option<(UncertainDecimal, Decimal128)> = None;
+    for m in measurements {
+        let sigmas = m.sigma_difference(&reference);
+        if sigmas.is_nan() {
+            continue;
+        }
+        match best {
+            Some((_, best_sigmas)) if !better(sigmas, best_sigmas) => {}
+            _ => best = Some((*m, sigmas)),
+        }
+    }
+
+    best.map(|(m, _)| m)
+}
+
+/// Groups the indices of `measurements` into clusters of agreeing results.
+///
+/// Agreement within `sigma` (see [`UncertainDecimal::agrees_with`]) is not
+/// transitive, so this uses single linkage: a measurement joins a cluster if
+/// it agrees with any member, and two members of a cluster need not agree
+/// with each other. Clusters are ordered by their first index, with indices
+/// ascending within each.
+pub fn cluster_consistent(measurements: &[UncertainDecimal], sigma: Decimal128) -> Vec<Vec<usize>> {
+    let mut assigned = vec![false; measurements.len()];
+    let mut clusters = Vec::new();
+
+    for start in 0..measurements.len() {
+        if assigned[start] {
+            continue;
+        }
+        assigned[start] = true;
+
+        let mut cluster = vec![start];
+        let mut next = 0;
+        while next < cluster.len() {
+            let member = measurements[cluster[next]];
+            for (i, m) in measurements.iter().enumerate() {
+                if !assigned[i] && member.agrees_with(m, sigma) {
+                    assigned[i] = true;
+                    cluster.push(i);
+                }
+            }
+            next += 1;
+        }
+
+        cluster.sort_unstable();
+        clusters.push(cluster);
+    }
+
+    clusters
+}
+
+/// Like [`reduce`], starting from `init`.
+pub fn fold(
+    decs: &[UncertainDecimal],
+    init: UncertainDecimal,
+    op: impl Fn(UncertainDecimal, UncertainDecimal) -> UncertainDecimal,
+) -> UncertainDecimal {
+    decs.iter().copied().fold(init, op).canonical()
+}
+
+/// Applies `op` to corresponding elements of `a` and `b`, in order.
+///
+/// Returns `None` if the slices differ in length.
+pub fn zip_propagate(
+    a: &[UncertainDecimal],
+    b: &[UncertainDecimal],
+    op: impl Fn(UncertainDecimal, UncertainDecimal) -> UncertainDecimal,
+) -> Option<Vec<UncertainDecimal>> {
+    if a.len() != b.len() {
+        return None;
+    }
+
+    Some(a.iter().zip(b).map(|(a, b)| op(*a, *b)).collect())
+}
+
+pub fn exact_base_pow(base: Decimal128, exp: UncertainDecimal) -> UncertainDecimal {
+    if base.is_negative() || base.is_zero() {
+        return UncertainDecimal::NAN;
+    }
+
+    let ln_base = decimal::ln(base);
+    let value = decimal::exp(exp.value * ln_base);
+
+    UncertainDecimal {
+        value,
+        uncertainty: decimal::abs(value * ln_base) * exp.uncertainty,
+    }
+    .canonical()
+}
+
+/// Divides `numerator` by every entry of `denominators` in turn, combining
+/// all relative uncertainties in one quadrature and canonicalizing once.
+///
+/// Returns an infinite value and uncertainty, signed like the numerator, if
+/// any denominator is zero.
+pub fn quotient(
+    numerator: UncertainDecimal,
+    denominators: &[UncertainDecimal],
+) -> UncertainDecimal {
+    if denominators.iter().any(|d| d.value.is_zero()) {
+        let infinity = decimal::infinity();
+        return UncertainDecimal {
+            value: if numerator.value.is_negative() {
+                -infinity
+            } else {
+                infinity
+            },
+            uncertainty: infinity,
+        };
+    }
+
+    let mut value = numerator.value;
+    let mut digits = numerator.value.digits();
+    let mut sum_sq_rel = Decimal128::ZERO;
+    for m in std::iter::once(&numerator).chain(denominators) {
+        if !m.is_exact() {
+            let rel = m.relative_uncertainty();
+            sum_sq_rel += rel * rel;
+        }
+    }
+    for d in denominators {
+        value /= d.value;
+        digits = min(digits, d.value.digits());
+    }
+
+    UncertainDecimal {
+        value: decimal::with_digits(value, digits),
+        uncertainty: decimal::sqrt(sum_sq_rel) * decimal::abs(value),
+    }
+    .canonical()
+}
+
+/// Evaluates the decay `A·exp(−t/τ)` at the exact time `t`, propagating the
+/// uncertainties of both the amplitude and the time constant.
+///
+/// Returns NaN if `tau` is zero.
+pub fn exp_decay(
+    amplitude: UncertainDecimal,
+    t: Decimal128,
+    tau: UncertainDecimal,
+) -> UncertainDecimal {
+    if tau.value.is_zero() {
+        return UncertainDecimal::NAN;
+    }
+
+    let decay = |tau: Decimal128| decimal::exp(-(t / tau));
+    UncertainDecimal::propagate2(
+        amplitude,
+        tau,
+        |a, tau| a * decay(tau),
+        |_, tau| decay(tau),
+        |a, tau| a * t / (tau * tau) * decay(tau),
+    )
+}
+
+/// Linearly interpolates the lookup table `xs → ys` at `x`.
+///
+/// The uncertainty combines the two bracketing `ys` uncertainties, weighted
+/// by their interpolation weights, with `x`'s uncertainty through the local
+/// slope. `xs` must be strictly ascending. Returns NaN rather than
+/// extrapolating if `x` lies outside the table, or if the slices differ in
+/// length or hold fewer than two points.
+pub fn interpolate(
+    x: UncertainDecimal,
+    xs: &[Decimal128],
+    ys: &[UncertainDecimal],
+) -> UncertainDecimal {
+    let nan = UncertainDecimal::NAN;
+    if xs.len() != ys.len() {
+        return nan;
+    }
+
+    let segment = xs
+        .windows(2)
+        .position(|edge| edge[0] <= x.value && x.value <= edge[1]);
+    let i = match segment {
+        Some(i) => i,
+        None => return nan,
+    };
+    let (x0, x1, y0, y1) = (xs[i], xs[i + 1], ys[i], ys[i + 1]);
+
+    let slope = (y1.value - y0.value) / (x1 - x0);
+    let t = (x.value - x0) / (x1 - x0);
+    let from_y0 = (Decimal128::ONE - t) * y0.uncertainty;
+    let from_y1 = t * y1.uncertainty;
+    let from_x = slope * x.uncertainty;
+
+    UncertainDecimal {
+        value: y0.value + slope * (x.value - x0),
+        uncertainty: decimal::sqrt(from_y0 * from_y0 + from_y1 * from_y1 + from_x * from_x),
+    }
+    .canonical()
+}
+
+/// The average of the pairwise ratios `num[i]/den[i]`.
+///
+/// Every pair counts equally, so a pair with a small, noisy denominator can
+/// dominate, and the nonlinearity of `1/den` biases the estimate upward when
+/// denominators are uncertain. The uncertainty is `√Σu(rᵢ)² / n`, with each
+/// ratio's `u(rᵢ)` propagated like `/`. Compare [`ratio_of_means`].
+///
+/// Returns NaN if the slices are empty or differ in length.
+pub fn mean_of_ratios(num: &[UncertainDecimal], den: &[UncertainDecimal]) -> UncertainDecimal {
+    if num.is_empty() || num.len() != den.len() {
+        return UncertainDecimal::NAN;
+    }
+
+    let len = Decimal128::from(num.len() as u64);
+    let ratios: Vec<UncertainDecimal> = num
+        .iter()
+        .zip(den)
+        .map(|(n, d)| n.div_uncanonical(*d))
+        .collect();
+
+    // The precision comes from the propagated uncertainty, not from how
+    // short the mean happens to be, so pad before canonicalizing.
+    UncertainDecimal {
+        value: decimal::with_digits(
+            ratios.iter().map(|r| r.value).sum::<Decimal128>() / len,
+            MAX_DIGITS as u32,
+        ),
+        uncertainty: decimal::sqrt(
+            ratios
+                .iter()
+                .map(|r| r.uncertainty * r.uncertainty)
+                .sum::<Decimal128>(),
+        ) / len,
+    }
+    .canonical()
+}
+
+/// The ratio `Σnum / Σden` of the totals, equal to the ratio of the means.
+///
+/// This weights each pair by its denominator, so it is less sensitive to
+/// small, noisy denominators than [`mean_of_ratios`]. The totals' absolute
+/// uncertainties add in quadrature, and their relative uncertainties then
+/// combine like `/`.
+///
+/// Returns NaN if the slices are empty or differ in length.
+pub fn ratio_of_means(num: &[UncertainDecimal], den: &[UncertainDecimal]) -> UncertainDecimal {
+    if num.is_empty() || num.len() != den.len() {
+        return UncertainDecimal::NAN;
+    }
+
+    let total = |decs: &[UncertainDecimal]| {
+        decs.iter()
+            .fold(UncertainDecimal::exact(Decimal128::ZERO), |acc, d| {
+                acc.add_uncanonical(*d)
+            })
+    };
+
+    let mut ratio = total(num).div_uncanonical(total(den));
+    ratio.value = decimal::with_digits(ratio.value, MAX_DIGITS as u32);
+
+    ratio.canonical()
+}
+
+/// Evaluates the calibration line `m·x + b`, including the slope-intercept
+/// covariance `cov_mb` in the propagated uncertainty.
+pub fn linear_model(
+    x: UncertainDecimal,
+    m: UncertainDecimal,
+    b: UncertainDecimal,
+    cov_mb: Decimal128,
+) -> UncertainDecimal {
+    let from_x = m.value * x.uncertainty;
+    let from_m = x.value * m.uncertainty;
+    let variance = from_x * from_x
+        + from_m * from_m
+        + b.uncertainty * b.uncertainty
+        + Decimal128::from(2) * x.value * cov_mb;
+
+    UncertainDecimal {
+        value: m.value * x.value + b.value,
+        uncertainty: decimal::sqrt(variance),
+    }
+    .canonical()
+}
+
+/// Evaluates the polynomial `Σ coeffs[i]·x^i` and its derivative together in
+/// one Horner sweep, propagating `x`'s uncertainty through the derivative.
+pub fn eval_polynomial(coeffs: &[Decimal128], x: UncertainDecimal) -> UncertainDecimal {
+    let mut value = Decimal128::ZERO;
+    let mut derivative = Decimal128::ZERO;
+    for c in coeffs.iter().rev() {
+        derivative = derivative * x.value + value;
+        value = value * x.value + *c;
+    }
+
+    UncertainDecimal {
+        value,
+        uncertainty: decimal::abs(derivative) * x.uncertainty,
+    }
+    .canonical()
+}
+
+pub fn midrange(decs: &[Decimal128]) -> Option<UncertainDecimal> {
+    let (lo, hi) = extrema(decs)?;
+    Some(UncertainDecimal::from_bounds(lo, hi))
+}
+
+pub fn range_span(decs: &[Decimal128]) -> Option<Decimal128> {
+    let (lo, hi) = extrema(decs)?;
+    Some(hi - lo)
+}
+
+fn extrema(decs: &[Decimal128]) -> Option<(Decimal128, Decimal128)> {
+    let (first, rest) = decs.split_first()?;
+
+    Some(rest.iter().fold((*first, *first), |(lo, hi), &d| {
+        (if d < lo { d } else { lo }, if d > hi { d } else { hi })
+    }))
+}
+
+/// Mean ± sample standard deviation of `decs`.
+///
+/// Like the other statistics here, this never panics on short input: the
+/// result is NaN if `decs` is empty, and the uncertainty is NaN for a single
+/// reading.
+pub fn average(decs: &[Decimal128]) -> UncertainDecimal {
+    if decs.is_empty() {
+        return UncertainDecimal::NAN;
+    }
+
+    let len = Decimal128::from(decs.len() as u64);
+    let avg: Decimal128 = decs.iter().sum::<Decimal128>() / len;
+
+    let std_dev = decimal::sqrt(
+        decs.iter()
+            .map(|d| {
+                let diff = *d - avg;
+                diff * diff
+            })
+            .sum::<Decimal128>()
+            / (len - Decimal128::ONE),
+    );
+
+    UncertainDecimal {
+        value: avg,
+        uncertainty: std_dev,
+    }
+}
+
+/// Like [`average`], but for readings that carry their own uncertainties.
+///
+/// The uncertainty is on the same per-reading scale as [`average`]'s: the
+/// larger of the sample standard deviation of the values and the RMS of the
+/// quoted uncertainties. This way neither scatter beyond the error bars nor
+/// error bars wider than the scatter are hidden. A single reading keeps its own
+/// uncertainty, and empty input gives NaN.
+pub fn average_with_uncertainties(measurements: &[UncertainDecimal]) -> UncertainDecimal {
+    let values: Vec<Decimal128> = measurements.iter().map(|m| m.value).collect();
+    let scatter = average(&values);
+    if measurements.is_empty() {
+        return scatter;
+    }
+
+    let len = Decimal128::from(measurements.len() as u64);
+    let rms = decimal::sqrt(
+        measurements
+            .iter()
+            .map(|m| m.uncertainty * m.uncertainty)
+            .sum::<Decimal128>()
+            / len,
+    );
+
+    UncertainDecimal {
+        uncertainty: if scatter.uncertainty > rms {
+            scatter.uncertainty
+        } else {
+            rms
+        },
+        ..scatter
+    }
+}
+
+/// Mean and standard deviation of each full window of `window` consecutive
+/// readings, in order.
+///
+/// Positions at the start and end of the series that would need a shorter
+/// window are skipped, so the result has `decs.len() - window + 1` entries, or
+/// none if `window` is zero or longer than the series.
+pub fn rolling_average(decs: &[Decimal128], window: usize) -> Vec<UncertainDecimal> {
+    if window == 0 {
+        return Vec::new();
+    }
+
+    decs.windows(window).map(average).collect()
+}
+
+/// Harmonic mean `n / Σ(1/x)`, with the standard error of the mean of the
+/// reciprocals propagated back as the uncertainty.
+///
+/// Returns NaN if any input is zero.
+pub fn harmonic_mean(decs: &[Decimal128]) -> UncertainDecimal {
+    if decs.iter().any(Decimal128::is_zero) {
+        return UncertainDecimal::NAN;
+    }
+
+    let reciprocals: Vec<Decimal128> = decs.iter().map(|d| Decimal128::ONE / *d).collect();
+    let UncertainDecimal {
+        value: mean,
+        uncertainty: std_dev,
+    } = average(&reciprocals);
+    let std_err = std_dev / decimal::sqrt(Decimal128::from(decs.len() as u64));
+
+    UncertainDecimal {
+        value: Decimal128::ONE / mean,
+        uncertainty: std_err / mean / mean,
+    }
+}
+
+/// Inverse-variance weighted mean of `measurements`, with an uncertainty that
+/// also reflects their scatter.
+///
+/// The internal uncertainty `1/√Σw` (with `w = 1/u²`) is multiplied by the
+/// Birge ratio `√(χ²/(n − 1))` when that exceeds one, i.e. when the points
+/// scatter more than their quoted uncertainties explain; it is never reduced.
+/// With a single measurement there is no scatter to assess.
+///
+/// Returns NaN if `measurements` is empty or any of them is exact.
+pub fn weighted_std_dev(measurements: &[UncertainDecimal]) -> UncertainDecimal {
+    if measurements.is_empty() || measurements.iter().any(UncertainDecimal::is_exact) {
+        return UncertainDecimal::NAN;
+    }
+
+    let weights: Vec<Decimal128> = measurements
+        .iter()
+        .map(|m| Decimal128::ONE / (m.uncertainty * m.uncertainty))
+        .collect();
+    let sum_w: Decimal128 = weights.iter().copied().sum();
+    let mean = measurements
+        .iter()
+        .zip(&weights)
+        .map(|(m, w)| m.value * *w)
+        .sum::<Decimal128>()
+        / sum_w;
+
+    let mut uncertainty = decimal::sqrt(Decimal128::ONE / sum_w);
+    if measurements.len() > 1 {
+        let chi_sq: Decimal128 = measurements
+            .iter()
+            .zip(&weights)
+            .map(|(m, w)| {
+                let diff = m.value - mean;
+                diff * diff * *w
+            })
+            .sum();
+        let birge = decimal::sqrt(chi_sq / Decimal128::from(measurements.len() as u64 - 1));
+        if birge > Decimal128::ONE {
+            uncertainty *= birge;
+        }
+    }
+
+    UncertainDecimal {
+        value: mean,
+        uncertainty,
+    }
+}
+
+#[macro_export]
+macro_rules! ud {
+    (@dec $x:expr) => {
+        <$crate::dec::Decimal128 as ::core::str::FromStr>::from_str(::core::stringify!($x)).unwrap()
+    };
+    ($v:expr) => {
+        $crate::UncertainDecimal::exact($crate::ud!(@dec $v))
+    };
+    ($v:expr, $u:literal %) => {
+        $crate::UncertainDecimal::from_relative(
+            $crate::ud!(@dec $v),
+            $crate::ud!(@dec $u)
+                / <$crate::dec::Decimal128 as ::core::convert::From<i32>>::from(100),
+        )
+    };
+    ($v:expr; $r:expr) => {
+        $crate::UncertainDecimal::from_relative($crate::ud!(@dec $v), $crate::ud!(@dec $r))
+    };
+    ($v:expr, $u:expr) => {
+        ($crate::UncertainDecimal {
+            value: $crate::ud!(@dec $v),
+            uncertainty: $crate::ud!(@dec $u),
+        })
+    };
+}
+
+/// Asserts that two [`UncertainDecimal`]s agree within `sigma` combined
+/// standard uncertainties (one if omitted), reporting the actual separation
+/// on failure.
+#[macro_export]
+macro_rules! assert_agrees {
+    ($a:expr, $b:expr $(,)?) => {
+        $crate::assert_agrees!($a, $b, $crate::dec::Decimal128::ONE)
+    };
+    ($a:expr, $b:expr, $sigma:expr $(,)?) => {
+        match (&$a, &$b, $sigma) {
+            (a, b, sigma) => {
+                let a: &$crate::UncertainDecimal = a;
+                let sigma: $crate::dec::Decimal128 = sigma;
+                if !a.agrees_with(b, sigma) {
+                    ::core::panic!(
+                        "assertion `a agrees with b` failed\n     a: {}\n     b: {}\n  diff: {}σ (tolerance {}σ)",
+                        a,
+                        b,
+                        a.sigma_difference(b),
+                        sigma,
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[test]
+fn test() {
+    let a = ud!(1.7775, 0.6);
+
+    println!("{}", a.canonical());
+
+    let b = ud!(2000, 0.3).canonical();
+
+    println!("{}", b);
+    println!("{}", a + b);
+
+    println!("{}", b.with_digits(8))
+}
+
+#[test]
+fn test_canonical_with_figures() {
+    let a = ud!(1.7775, 0.634);
+
+    assert_eq!(a.canonical().to_string(), "1.8 ± 0.6");
+    assert_eq!(a.canonical_with_figures(2).to_string(), "1.78 ± 0.63");
+    assert_eq!(a.canonical_with_figures(0), a.canonical());
+    assert_eq!(ud!(1.25, 0.0755).canonical().to_string(), "1.25 ± 0.08");
+}
+
+#[test]
+fn test_ud_variants() {
+    assert_eq!(ud!(42).to_string(), "42 ± 0");
+    assert_eq!(ud!(-50, 2%).canonical().to_string(), "-50 ± 1");
+    assert_eq!(ud!(-50; 0.02).canonical().to_string(), "-50 ± 1");
+    assert_eq!(ud!(1.5, 0.1).to_string(), "1.5 ± 0.1");
+}
+
+#[test]
+fn test_ud_without_imports() {
+    mod no_imports {
+        pub fn build() -> [crate::UncertainDecimal; 4] {
+            [ud!(42), ud!(-50, 2%), ud!(-50; 0.02), ud!(1.5, 0.1)]
+        }
+    }
+
+    let [a, b, c, d] = no_imports::build();
+    assert_eq!(a.to_string(), "42 ± 0");
+    assert_eq!(b.to_string(), c.to_string());
+    assert_eq!(d.to_string(), "1.5 ± 0.1");
+}
+
+#[test]
+fn test_range_conversion() {
+    let range = ud!(1.0).value..=ud!(1.4).value;
+    let a: UncertainDecimal = range.into();
+    assert_eq!(a.to_string(), "1.2 ± 0.2");
+
+    let range: RangeInclusive<Decimal128> = ud!(9.81, 0.02).into();
+    assert_eq!(range.start().to_string(), "9.79");
+    assert_eq!(range.end().to_string(), "9.83");
+}
+
+#[test]
+fn test_exact_fast_path() {
+    let cases = [
+        (ud!(1.5, 0), ud!(2.5, 0), ["4.0 ± 0", "3.75 ± 0", "0.6 ± 0"]),
+        (
+            ud!(12, 0.0),
+            ud!(0.25, 0),
+            ["12.25 ± 0", "3.00 ± 0", "48 ± 0"],
+        ),
+        (
+            ud!(-1.234, 0),
+            ud!(20, 0.00),
+            ["18.766 ± 0", "-24.680 ± 0", "-0.0617 ± 0"],
+        ),
+        (ud!(3), ud!(1.5), ["4.5 ± 0", "4.5 ± 0", "2 ± 0"]),
+    ];
+
+    for (a, b, [sum, product, quotient]) in cases {
+        assert!(a.is_exact() && b.is_exact());
+        assert_eq!((a + b).to_string(), sum);
+        assert_eq!((a * b).to_string(), product);
+        assert_eq!((a / b).to_string(), quotient);
+        assert!((a + b).is_exact() && (a * b).is_exact() && (a / b).is_exact());
+    }
+
+    // A measured operand sets the digits; the exact one does not limit them.
+    assert_eq!((ud!(2.0, 0.1) * ud!(3)).to_string(), "6.0 ± 0.3");
+    assert_eq!((ud!(2.00, 0.01) / ud!(8)).to_string(), "0.250 ± 0.001");
+    assert!(!ud!(1.5, 0.1).is_exact());
+}
+
+#[test]
+fn test_canonical_exact() {
+    // Exact values are not rounded to the exponent of their zero uncertainty.
+    assert_eq!(ud!(1.5, 0).canonical().to_string(), "1.5 ± 0");
+    assert_eq!(ud!(1.50, 0.00).canonical().to_string(), "1.50 ± 0");
+    assert_eq!(ud!(1.5E+3, 0E+2).canonical().to_string(), "1.5E+3 ± 0");
+    assert_eq!(
+        ud!(1.5, 0).canonical_with_figures(3),
+        ud!(1.5, 0).canonical()
+    );
+    assert_eq!(ud!(1.5, 0).canonical().uncertainty.exponent(), 0);
+}
+
+#[test]
+fn test_powf_const() {
+    assert_eq!(
+        ud!(4.00, 0.02).powf_const(ud!(1.5).value).to_string(),
+        "8.00 ± 0.06"
+    );
+    assert_eq!(
+        ud!(-2.00, 0.02).powf_const(ud!(3).value).to_string(),
+        "-8.0 ± 0.2"
+    );
+    assert_eq!(
+        ud!(-2.00, 0.02).powf_const(ud!(3.0).value).to_string(),
+        "-8.0 ± 0.2"
+    );
+
+    // The cube root comes back from `pow` as 3.999…, which must round up.
+    let third = Decimal128::ONE / Decimal128::from(3);
+    assert!(decimal::pow(ud!(64).value, third) < ud!(4).value);
+    assert_eq!(ud!(64.0, 3).powf_const(third).to_string(), "4.00 ± 0.06");
+
+    let sqrt2 = ud!(2).powf_const(ud!(0.5).value);
+    assert!(sqrt2.is_exact());
+    assert_eq!(
+        sqrt2.value.to_string(),
+        "1.414213562373095048801688724209698"
+    );
+
+    let nan = ud!(-4.00, 0.02).powf_const(ud!(1.5).value);
+    assert!(nan.value.is_nan() && nan.uncertainty.is_nan());
+}
+
+#[test]
+fn test_canonical_all() {
+    let mut decs = [
+        ud!(1.7775, 0.6),
+        ud!(2000, 0.3),
+        ud!(-0.012345, 0.00071),
+        ud!(3.5, 0),
+    ];
+    let expected = decs.map(UncertainDecimal::canonical);
+
+    canonical_all(&mut decs);
+
+    for (dec, expected) in decs.iter().zip(expected) {
+        assert_eq!(dec.to_string(), expected.to_string());
+    }
+}
+
+#[test]
+fn test_parse_concise() {
+    let g = UncertainDecimal::from_str("6.674(15)e-11").unwrap();
+    assert_eq!(
+        g.value.to_string(),
+        Decimal128::from_str("6.674e-11").unwrap().to_string()
+    );
+    assert_eq!(
+        g.uncertainty.to_string(),
+        Decimal128::from_str("0.015e-11").unwrap().to_string()
+    );
+
+    let n = UncertainDecimal::from_str("6.02214(3)E+23").unwrap();
+    assert_eq!(n.value, Decimal128::from_str("6.02214e23").unwrap());
+    assert_eq!(n.uncertainty, Decimal128::from_str("3e18").unwrap());
+
+    let m = UncertainDecimal::from_str("-1.2345(12)").unwrap();
+    assert_eq!(m.to_string(), "-1.2345 ± 0.0012");
+
+    assert_eq!(
+        UncertainDecimal::from_str("12.3(1.5)").unwrap().to_string(),
+        "12.3 ± 1.5"
+    );
+
+    assert!(UncertainDecimal::from_str("6.674(15)x-11").is_err());
+    assert!(UncertainDecimal::from_str("6.674()").is_err());
+    assert!(UncertainDecimal::from_str("6.674").is_err());
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!(
+        format!("{:?}", ud!(1.50, 0.020)),
+        "UncertainDecimal { value: 1.50, value_exponent: -2, value_digits: 3, \
+         uncertainty: 0.020, uncertainty_exponent: -3, uncertainty_digits: 2 }"
+    );
+}
+
+#[test]
+fn test_clamp_relative_minimum() {
+    let one_percent = ud!(0.01).value;
+
+    assert_eq!(
+        ud!(12.345, 0.001)
+            .clamp_relative_minimum(one_percent)
+            .to_string(),
+        "12.3 ± 0.1"
+    );
+    assert_eq!(
+        ud!(-12.345, 0.001)
+            .clamp_relative_minimum(one_percent)
+            .to_string(),
+        "-12.3 ± 0.1"
+    );
+    assert_eq!(
+        ud!(12.345, 0.5)
+            .clamp_relative_minimum(one_percent)
+            .to_string(),
+        "12.3 ± 0.5"
+    );
+    assert_eq!(
+        ud!(0.00, 0.02)
+            .clamp_relative_minimum(one_percent)
+            .to_string(),
+        "0.00 ± 0.02"
+    );
+}
+
+#[test]
+fn test_clamp_uncertainty_minimum() {
+    let floor = ud!(0.05).value;
+
+    assert_eq!(
+        ud!(1.2345, 0.001)
+            .clamp_uncertainty_minimum(floor)
+            .to_string(),
+        "1.23 ± 0.05"
+    );
+    assert_eq!(
+        ud!(1.2345, 0.3)
+            .clamp_uncertainty_minimum(floor)
+            .to_string(),
+        "1.2 ± 0.3"
+    );
+    assert_eq!(
+        ud!(7, 0)
+            .clamp_uncertainty_minimum(ud!(1).value)
+            .to_string(),
+        "7 ± 1"
+    );
+}
+
+#[test]
+fn test_total_relative_uncertainty() {
+    let measurements = [ud!(10, 0.3), ud!(-5, 0.2), ud!(0, 1)];
+
+    assert_eq!(ud!(-5, 0.2).relative_uncertainty(), ud!(0.04).value);
+    assert_eq!(total_relative_uncertainty(&measurements), ud!(0.05).value);
+    assert!(total_relative_uncertainty(&[]).is_zero());
+}
+
+#[test]
+fn test_ref_ops() {
+    let decs = [ud!(1.50, 0.02), ud!(2.25, 0.03)];
+    let (a, b) = (&decs[0], &decs[1]);
+
+    for (by_ref, by_value) in [
+        (a + b, *a + *b),
+        (*a + b, *a + *b),
+        (a - *b, *a - *b),
+        (a * b, *a * *b),
+        (a / b, *a / *b),
+    ] {
+        assert_eq!(by_ref.to_string(), by_value.to_string());
+    }
+}
+
+#[test]
+fn test_midrange() {
+    let decs = [
+        ud!(1.2).value,
+        ud!(0.9).value,
+        ud!(1.5).value,
+        ud!(1.1).value,
+    ];
+
+    assert_eq!(midrange(&decs).unwrap().to_string(), "1.2 ± 0.3");
+    assert_eq!(range_span(&decs).unwrap(), ud!(0.6).value);
+    assert!(midrange(&[]).is_none());
+    assert!(range_span(&[]).is_none());
+}
+
+#[test]
+fn test_split_strings() {
+    for a in [ud!(1.7775, 0.6), ud!(2000, 0.3), ud!(6.674e-11, 1.5e-13)] {
+        assert_eq!(
+            format!("{} ± {}", a.to_value_string(), a.to_uncertainty_string()),
+            a.canonical().to_string()
+        );
+    }
+
+    assert_eq!(ud!(9.8123, 0.02).to_value_string(), "9.81");
+    assert_eq!(ud!(9.8123, 0.02).to_uncertainty_string(), "0.02");
+}
+
+#[test]
+fn test_exact_base_pow() {
+    let two = ud!(2).value;
+
+    assert_eq!(
+        exact_base_pow(two, ud!(3.00, 0.01)).to_string(),
+        "8.00 ± 0.06"
+    );
+    let ten = ud!(10).value;
+    assert_eq!(
+        exact_base_pow(ten, ud!(2.0, 0.001)).to_string(),
+        "100.0 ± 0.2"
+    );
+
+    // An exact exponent keeps the full precision of the computed power.
+    let exact = exact_base_pow(two, ud!(3.00));
+    assert!(exact.is_exact());
+    assert!(decimal::approx_eq(exact.value, ud!(8).value, 2));
+
+    for base in [ud!(0).value, ud!(-2).value] {
+        let nan = exact_base_pow(base, ud!(3.00, 0.01));
+        assert!(nan.value.is_nan() && nan.uncertainty.is_nan());
+    }
+}
+
+#[test]
+fn test_reduce() {
+    // The exact sum is 10.26 ± 0.12; rounding every step drifts the value.
+    let decs = [ud!(1.14, 0.04); 9];
+    let naive = reduce(&decs, |a, b| a + b).unwrap();
+    let fused = reduce(&decs, UncertainDecimal::add_uncanonical).unwrap();
+    assert_eq!(naive.to_string(), "10.1 ± 0.1");
+    assert_eq!(fused.to_string(), "10.3 ± 0.1");
+    assert_ne!(naive, fused);
+
+    // Likewise for a chain of products, 1.15⁹ = 3.52 ± 0.09.
+    let decs = [ud!(1.15, 0.01); 9];
+    let naive = reduce(&decs, |a, b| a * b).unwrap();
+    let fused = reduce(&decs, UncertainDecimal::mul_uncanonical).unwrap();
+    assert_eq!(naive.to_string(), "3.5 ± 0.1");
+    assert_eq!(fused.to_string(), "3.52 ± 0.09");
+    assert_ne!(naive, fused);
+
+    let product = fold(
+        &[ud!(1.00, 0.04); 3],
+        ud!(2),
+        UncertainDecimal::mul_uncanonical,
+    );
+    assert_eq!(product.to_string(), "2.0 ± 0.1");
+    assert!(reduce(&[], UncertainDecimal::add_uncanonical).is_none());
+}
+
+#[test]
+fn test_harmonic_mean() {
+    let decs = [ud!(1).value, ud!(2).value, ud!(4).value];
+    assert_eq!(harmonic_mean(&decs).canonical().to_string(), "1.7 ± 0.6");
+
+    let nan = harmonic_mean(&[ud!(1).value, ud!(0).value]);
+    assert!(nan.value.is_nan() && nan.uncertainty.is_nan());
+}
+
+#[test]
+fn test_format_with_units() {
+    assert_eq!(ud!(1.5E+3).format_with_units("m"), "(1.5 ± 0)E+3 m");
+    assert_eq!(
+        ud!(9.8123, 0.02).format_with_units("m/s²"),
+        "9.81 ± 0.02 m/s²"
+    );
+    assert_eq!(ud!(9.8123, 0.02).format_with_units(""), "9.81 ± 0.02");
+    assert_eq!(
+        UncertainDecimal::from_str("6.674(15)e-11")
+            .unwrap()
+            .format_with_units("m³/(kg·s²)"),
+        "(6.67 ± 0.02)E-11 m³/(kg·s²)"
+    );
+    assert_eq!(
+        UncertainDecimal::from_str("6.02214(3)E+23")
+            .unwrap()
+            .format_with_units("mol⁻¹"),
+        "(6.02214 ± 0.00003)E+23 mol⁻¹"
+    );
+}
+
+#[test]
+fn test_saturating() {
+    let huge = ud!(9.0E+6144, 0.1E+6144);
+
+    let sum = huge.saturating_add(huge);
+    assert_eq!(sum.value, decimal::max());
+    assert!(sum.uncertainty.is_infinite());
+
+    let product = (-huge).saturating_mul(huge);
+    assert_eq!(product.value, -decimal::max());
+    assert!(product.uncertainty.is_infinite());
+
+    let small = ud!(1.0E+3000, 0.1E+3000);
+    assert_eq!(small.saturating_add(small), small + small);
+    assert_eq!(
+        ud!(1.50, 0.02).saturating_mul(ud!(2.00, 0.01)),
+        ud!(1.50, 0.02) * ud!(2.00, 0.01)
+    );
+
+    // Only overflow saturates; an operand that is already infinite stays so.
+    let infinite = ud!(Infinity, 0.1);
+    assert!(infinite.saturating_add(ud!(1, 0.1)).value.is_infinite());
+    assert!(infinite.saturating_mul(ud!(2, 0.1)).value.is_infinite());
+    assert!((-infinite).saturating_mul(huge).value.is_infinite());
+}
+
+#[test]
+fn test_non_finite_propagation() {
+    let finite = ud!(2.0, 0.1);
+    let nan_value = ud!(NaN, 0.1);
+    let nan_uncertainty = ud!(2.0, NaN);
+    let infinite = ud!(Infinity, 0.1);
+
+    for result in [
+        nan_value + finite,
+        finite - nan_value,
+        nan_uncertainty * finite,
+        finite / nan_uncertainty,
+        ud!(NaN) * ud!(2),
+        ud!(0, 0.1) * infinite,
+        infinite - infinite,
+    ] {
+        assert!(result.value.is_nan(), "{:?}", result);
+        assert!(result.uncertainty.is_nan(), "{:?}", result);
+    }
+
+    for result in [finite * infinite, infinite + finite, ud!(2) * ud!(Infinity)] {
+        assert!(result.value.is_infinite(), "{:?}", result);
+        assert!(result.uncertainty.is_infinite(), "{:?}", result);
+    }
+}
+
+#[test]
+fn test_matching_digits() {
+    let a = ud!(6.67430, 0.00015);
+
+    assert_eq!(a.matching_digits(&ud!(6.67408, 0.0003)), 4);
+    assert_eq!(a.matching_digits(&ud!(6.6, 0.1)), 2);
+    assert_eq!(a.matching_digits(&ud!(66.7430, 0.0015)), 0);
+    assert_eq!(a.matching_digits(&ud!(-6.67430, 0.00015)), 0);
+    assert_eq!(ud!(9.99, 0.01).matching_digits(&ud!(10.0, 0.1)), 0);
+    assert_eq!(
+        ud!(0.0012, 0.0001).matching_digits(&ud!(0.00125, 0.00001)),
+        2
+    );
+}
+
+#[test]
+fn test_linear_model() {
+    let (x, m, b) = (ud!(4.00, 0.05), ud!(2.50, 0.05), ud!(1.00, 0.10));
+
+    let independent = m.mul_uncanonical(x).add_uncanonical(b).canonical();
+    assert_eq!(linear_model(x, m, b, Decimal128::ZERO), independent);
+    assert_eq!(independent.to_string(), "11.0 ± 0.3");
+
+    let correlated = linear_model(x, m, b, ud!(-0.004).value);
+    assert_eq!(correlated.to_string(), "11.0 ± 0.2");
+}
+
+#[test]
+fn test_try_parse() {
+    use ParseUncertainDecimalError::*;
+
+    assert_eq!(
+        UncertainDecimal::try_parse("1.5 ± 0.1")
+            .unwrap()
+            .to_string(),
+        "1.5 ± 0.1"
+    );
+    assert_eq!(UncertainDecimal::try_parse("1.5"), Err(MissingUncertainty));
+    assert_eq!(UncertainDecimal::try_parse("x ± 0.1"), Err(InvalidValue));
+    assert_eq!(
+        UncertainDecimal::try_parse("1.5 ± x"),
+        Err(InvalidUncertainty)
+    );
+    assert_eq!(UncertainDecimal::try_parse("x(1)"), Err(InvalidValue));
+    assert_eq!(
+        UncertainDecimal::try_parse("1.5(-1)"),
+        Err(InvalidUncertainty)
+    );
+    assert_eq!(
+        UncertainDecimal::try_parse("1.5(1"),
+        Err(InvalidUncertainty)
+    );
+    assert_eq!(UncertainDecimal::try_parse("1.5(1)e"), Err(InvalidExponent));
+    assert_eq!(InvalidExponent.to_string(), "invalid exponent");
+}
+
+#[test]
+fn test_contains() {
+    let window = ud!(10.0, 0.5);
+
+    assert!(window.contains(ud!(9.5).value));
+    assert!(window.contains(ud!(10.5).value));
+    assert!(!window.contains(ud!(10.6).value));
+    assert!(window.contains_sigma(ud!(11.4).value, ud!(3).value));
+    assert!(!window.contains_sigma(ud!(11.6).value, ud!(3).value));
+    assert!(!ud!(NaN, 0.5).contains(ud!(10).value));
+}
+
+#[test]
+fn test_converged() {
+    let previous = ud!(10.00, 0.03);
+
+    assert_eq!(ud!(10.05, 0.04).sigma_difference(&previous), ud!(1).value);
+    assert!(ud!(10.05, 0.04).converged(&previous, ud!(1).value));
+    assert!(!ud!(10.06, 0.04).converged(&previous, ud!(1).value));
+    assert!(ud!(2).converged(&ud!(2.0), Decimal128::ZERO));
+    assert!(!ud!(2).converged(&ud!(2.1), ud!(100).value));
+    assert!(!ud!(NaN, 0.1).converged(&previous, ud!(100).value));
+}
+
+#[test]
+fn test_value_comparisons() {
+    let readings = [ud!(9.79, 0.02), ud!(9.81, 0.02), ud!(9.83, 0.02)];
+    let threshold = ud!(9.80).value;
+
+    let above: Vec<_> = readings.iter().filter(|m| m.gt_value(threshold)).collect();
+    assert_eq!(above.len(), 2);
+    assert!(readings[0].lt_value(threshold));
+    assert!(readings[1].eq_value(ud!(9.810).value));
+    assert!(!readings[1].eq_value(threshold));
+    assert!(!ud!(NaN, 0.1).lt_value(threshold) && !ud!(NaN, 0.1).gt_value(threshold));
+}
+
+#[test]
+fn test_pull() {
+    assert_eq!(ud!(10.06, 0.03).pull(ud!(10.00).value), ud!(2).value);
+    assert_eq!(ud!(9.94, 0.03).pull(ud!(10.00).value), ud!(-2).value);
+
+    let above = ud!(10.06).pull(ud!(10.00).value);
+    assert!(above.is_infinite() && !above.is_negative());
+    let below = ud!(9.94).pull(ud!(10.00).value);
+    assert!(below.is_infinite() && below.is_negative());
+    assert!(ud!(10.00).pull(ud!(10.00).value).is_nan());
+}
+
+#[test]
+fn test_is_significant() {
+    let peak = ud!(12.0, 0.5);
+
+    assert!(peak.is_significant(ud!(10.0).value, ud!(3).value));
+    assert!(!peak.is_significant(ud!(10.5).value, ud!(3).value));
+    assert!(!peak.is_significant(ud!(14.0).value, ud!(0).value));
+    assert!(ud!(0.2).is_significant(Decimal128::ZERO, ud!(5).value));
+    assert!(!ud!(NaN, 0.5).is_significant(Decimal128::ZERO, ud!(1).value));
+}
+
+#[test]
+fn test_add_precision_by_place() {
+    assert_eq!((ud!(1000, 1) + ud!(0.5, 0.1)).to_string(), "1001 ± 1");
+    assert_eq!((ud!(1000, 1) - ud!(0.4, 0.1)).to_string(), "1000 ± 1");
+    assert_eq!(
+        (ud!(12.345, 0.01) + ud!(0.03, 0.01)).to_string(),
+        "12.38 ± 0.01"
+    );
+
+    // Exact operands do not coarsen the place of the result.
+    assert_eq!((ud!(1.5, 0.1) + ud!(2)).to_string(), "3.5 ± 0.1");
+    assert_eq!((ud!(1.52, 0.01) + ud!(10)).to_string(), "11.52 ± 0.01");
+    assert_eq!((ud!(12) + ud!(0.25)).to_string(), "12.25 ± 0");
+    assert_eq!((ud!(10) - ud!(1.52, 0.01)).to_string(), "8.48 ± 0.01");
+}
+
+#[test]
+fn test_decimal_functions() {
+    assert_eq!(decimal::pow(ud!(2).value, ud!(10).value), ud!(1024).value);
+    assert_eq!(decimal::pow(ud!(4).value, ud!(0.5).value), ud!(2).value);
+    assert_eq!(decimal::exp(Decimal128::ZERO), Decimal128::ONE);
+    assert_eq!(decimal::ln(Decimal128::ONE), Decimal128::ZERO);
+    assert!(decimal::approx_eq(
+        decimal::ln(decimal::exp(Decimal128::ONE)),
+        Decimal128::ONE,
+        2
+    ));
+}
+
+#[test]
+fn test_approx_eq() {
+    let third = Decimal128::ONE / Decimal128::from(3);
+    let sqrt_ninth = decimal::sqrt(third * third);
+
+    assert!(decimal::approx_eq(sqrt_ninth, third, 1));
+    assert!(decimal::approx_eq(ud!(9.81).value, ud!(9.810).value, 0));
+    assert!(decimal::approx_eq(
+        ud!(1.000000000000000000000000000000000).value,
+        ud!(1.000000000000000000000000000000002).value,
+        2
+    ));
+    assert!(!decimal::approx_eq(
+        ud!(1.000000000000000000000000000000000).value,
+        ud!(1.000000000000000000000000000000003).value,
+        2
+    ));
+    assert!(!decimal::approx_eq(ud!(-1E-40).value, ud!(1E-40).value, 0));
+    assert!(!decimal::approx_eq(Decimal128::NAN, Decimal128::NAN, 100));
+    assert!(decimal::approx_eq(
+        decimal::infinity(),
+        decimal::infinity(),
+        0
+    ));
+}
+
+#[test]
+fn test_propagate2() {
+    let (a, b) = (ud!(3.00, 0.04), ud!(2.00, 0.03));
+
+    let product = UncertainDecimal::propagate2(a, b, |a, b| a * b, |_, b| b, |a, _| a);
+    assert_eq!(product, a.mul_uncanonical(b).canonical());
+    assert_eq!(product.to_string(), "6.0 ± 0.1");
+
+    let difference = UncertainDecimal::propagate2_with_covariance(
+        a,
+        b,
+        |a, b| a - b,
+        |_, _| Decimal128::ONE,
+        |_, _| -Decimal128::ONE,
+        ud!(0.0012).value,
+    );
+    assert_eq!(difference.to_string(), "1.00 ± 0.01");
+}
+
+#[test]
+fn test_uncertainty_sign() {
+    let negative_zero = UncertainDecimal {
+        value: ud!(1.5).value,
+        uncertainty: Decimal128::from_str("-0").unwrap(),
+    };
+    assert_eq!(negative_zero.canonical().to_string(), "1.5 ± 0");
+
+    let negative = UncertainDecimal {
+        value: ud!(1.5).value,
+        uncertainty: ud!(-0.1).value,
+    };
+    assert_eq!(negative.canonical().to_string(), "1.5 ± 0.1");
+
+    assert_eq!((ud!(-2.0, 0.1) * ud!(3.0, 0.1)).to_string(), "-6.0 ± 0.4");
+    assert_eq!((ud!(-2.0, 0.1) / ud!(3.0, 0.1)).to_string(), "-0.67 ± 0.04");
+}
+
+#[test]
+fn test_rolling_average() {
+    let decs = [1, 3, 2, 6, 4].map(Decimal128::from);
+
+    let rolling = rolling_average(&decs, 3);
+    assert_eq!(rolling.len(), 3);
+    assert_eq!(rolling[0], average(&decs[0..3]));
+    assert_eq!(rolling[2].canonical().to_string(), "4 ± 2");
+
+    assert!(rolling_average(&decs, 0).is_empty());
+    assert!(rolling_average(&decs, 6).is_empty());
+}
+
+#[test]
+fn test_map_uncertainty() {
+    let k = Decimal128::from(2);
+
+    assert_eq!(
+        ud!(9.812, 0.013).map_uncertainty(|u| u * k).to_string(),
+        "9.81 ± 0.03"
+    );
+    assert_eq!(
+        ud!(9.812, 0.013)
+            .map_uncertainty(|_| Decimal128::ZERO)
+            .to_string(),
+        "9.812 ± 0"
+    );
+}
+
+#[test]
+fn test_parse_asymmetric() {
+    use ParseUncertainDecimalError::*;
+
+    assert_eq!(
+        UncertainDecimal::parse_asymmetric("1.2 +0.3 -0.1"),
+        Ok((ud!(1.2).value, ud!(0.3).value, ud!(0.1).value))
+    );
+    assert_eq!(
+        UncertainDecimal::parse_asymmetric("-1.2E+3 -0.1E+3 +0.3E+3"),
+        Ok((ud!(-1.2E+3).value, ud!(0.3E+3).value, ud!(0.1E+3).value))
+    );
+    assert_eq!(
+        UncertainDecimal::from_str("1.2 +0.1 -0.3")
+            .unwrap()
+            .to_string(),
+        "1.2 ± 0.3"
+    );
+
+    assert_eq!(
+        UncertainDecimal::try_parse("1.2 +0.3"),
+        Err(MissingUncertainty)
+    );
+    assert_eq!(
+        UncertainDecimal::try_parse("1.2 +0.3 +0.1"),
+        Err(InvalidUncertainty)
+    );
+    assert_eq!(
+        UncertainDecimal::try_parse("1.2 +0.3 0.1"),
+        Err(InvalidUncertainty)
+    );
+    assert_eq!(
+        UncertainDecimal::try_parse("1.2 +0.3 --0.1"),
+        Err(InvalidUncertainty)
+    );
+}
+
+#[test]
+fn test_exp10() {
+    assert_eq!(ud!(2.000, 0.001).exp10().to_string(), "100.0 ± 0.2");
+    assert_eq!(ud!(-7.40, 0.01).exp10().to_string(), "3.98E-8 ± 9E-10");
+    assert_eq!(ud!(3).exp10().to_string(), "1E+3 ± 0");
+    assert_eq!(
+        ud!(-2).exp10(),
+        UncertainDecimal::pow10_exact(ud!(-2).value)
+    );
+    assert_eq!(
+        UncertainDecimal::pow10_exact(ud!(-2).value).to_string(),
+        "0.01 ± 0"
+    );
+    assert!(UncertainDecimal::pow10_exact(ud!(0.5).value).value.is_nan());
+
+    // Integral exponents with trailing zeros still take the exact path.
+    for exp in [ud!(2.0), ud!(2.00)] {
+        assert_eq!(exp.exp10().to_string(), "1E+2 ± 0");
+        assert_eq!(exp.exp10().value, ud!(100).value);
+    }
+
+    for exp in [ud!(7000), ud!(-7000)] {
+        let nan = UncertainDecimal::pow10_exact(exp.value);
+        assert!(nan.value.is_nan() && nan.uncertainty.is_nan());
+    }
+}
+
+#[test]
+fn test_tuple_conversion() {
+    let (value, uncertainty) = ud!(9.81, 0.02).as_tuple();
+    assert_eq!(
+        (value.to_string(), uncertainty.to_string()),
+        ("9.81".into(), "0.02".into())
+    );
+
+    let a: UncertainDecimal = (ud!(9.8132).value, ud!(0.0234).value).into();
+    assert_eq!(a.to_string(), "9.81 ± 0.02");
+    assert_eq!(UncertainDecimal::from(a.as_tuple()), a);
+}
+
+#[test]
+fn test_uncertainty_budget() {
+    let budget = uncertainty_budget(
+        &[("length", ud!(10.0, 0.3)), ("offset", ud!(2.0, 0.4))],
+        BudgetMode::Additive,
+    );
+    assert_eq!(
+        budget,
+        [
+            ("length".to_string(), ud!(0.36).value),
+            ("offset".to_string(), ud!(0.64).value)
+        ]
+    );
+
+    let budget = uncertainty_budget(
+        &[("mass", ud!(2.0, 0.02)), ("volume", ud!(4.0, 0.12))],
+        BudgetMode::Multiplicative,
+    );
+    assert_eq!(budget[0].1.to_string(), "0.1");
+    assert_eq!(budget[1].1.to_string(), "0.9");
+
+    let budget = uncertainty_budget(&[("exact", ud!(1.0))], BudgetMode::Additive);
+    assert!(budget[0].1.is_nan());
+}
+
+#[test]
+fn test_checked_canonical() {
+    let (a, lost) = ud!(9.8132, 0.02).checked_canonical();
+    assert_eq!(a.to_string(), "9.81 ± 0.02");
+    assert!(lost);
+
+    let (a, lost) = ud!(9.8100, 0.02).checked_canonical();
+    assert_eq!(a.to_string(), "9.81 ± 0.02");
+    assert!(!lost);
+
+    let (a, lost) = ud!(9.8, 0.02).checked_canonical();
+    assert_eq!(a.to_string(), "9.8 ± 0.1");
+    assert!(!lost);
+
+    assert!(
+        !UncertainDecimal::exact(Decimal128::NAN)
+            .checked_canonical()
+            .1
+    );
+}
+
+#[test]
+fn test_to_relative_string() {
+    assert_eq!(ud!(9.81, 0.04).to_relative_string(), "±0.4%");
+    assert_eq!(ud!(9.81, 0.05).to_relative_string(), "±0.5%");
+    assert_eq!(ud!(2.0, 0.25).to_relative_string(), "±13%");
+    assert_eq!(ud!(-1.0, 3).to_relative_string(), "±300%");
+    assert_eq!(ud!(0.0, 0.1).to_relative_string(), "±∞%");
+    assert_eq!(ud!(1.5).to_relative_string(), "±0%");
+    assert_eq!(ud!(2.0, -0.1).to_relative_string(), "±5%");
+    assert_eq!(ud!(2.0, -0.1).relative_uncertainty(), ud!(0.05).value);
+}
+
+#[test]
+fn test_monus() {
+    assert_eq!(ud!(5.0, 0.3).monus(ud!(2.0, 0.4)).to_string(), "3.0 ± 0.5");
+    assert_eq!(ud!(2.0, 0.3).monus(ud!(1.9, 0.4)).to_string(), "0.1 ± 0.5");
+    assert_eq!(ud!(2.0, 0.3).monus(ud!(5.0, 0.4)).to_string(), "0.0 ± 0.5");
+
+    // A clamped zero is not exact: −0.1 ± 0.5 may well be positive.
+    let zero = ud!(2.0, 0.3).monus(ud!(2.1, 0.4));
+    assert_eq!(zero.to_string(), "0.0 ± 0.5");
+    assert!(!zero.is_exact());
+}
+
+#[test]
+fn test_canonical_coarse_value() {
+    assert_eq!(ud!(123456789, 0.5).canonical().to_string(), "123456789 ± 1");
+    assert_eq!(
+        ud!(123456789, 0.05).canonical().to_string(),
+        "123456789 ± 1"
+    );
+    assert_eq!(
+        ud!(123456789, 1.5).canonical_with_figures(2).to_string(),
+        "123456789 ± 2"
+    );
+    assert_eq!(
+        ud!(1.2E+3, 150).canonical_with_figures(2).to_string(),
+        "1.2E+3 ± 2E+2"
+    );
+}
+
+#[test]
+fn test_quotient() {
+    let a = ud!(12.0, 0.3);
+    let denominators = [ud!(2.00, 0.04), ud!(3.00, 0.06)];
+    assert_eq!(quotient(a, &denominators).to_string(), "2.00 ± 0.08");
+    assert_eq!(quotient(a, &[]).to_string(), "12.0 ± 0.3");
+    assert_eq!(quotient(ud!(6), &[ud!(2), ud!(3)]).to_string(), "1 ± 0");
+
+    let zero = quotient(-a, &[ud!(2.0, 0.1), ud!(0.0, 0.1)]);
+    assert!(zero.value.is_infinite() && zero.value.is_negative());
+    assert!(zero.uncertainty.is_infinite());
+}
+
+#[test]
+fn test_uncertainty_ulps() {
+    assert_eq!(ud!(12.345, 0.002).uncertainty_ulps().to_string(), "2");
+    assert_eq!(ud!(12.35, 0.015).uncertainty_ulps().to_string(), "1.5");
+    assert_eq!(ud!(4.7E+3, 2E+2).uncertainty_ulps().to_string(), "2");
+    assert_eq!(ud!(12.345).uncertainty_ulps(), Decimal128::ZERO);
+
+    let reading = ud!(12.345).value;
+    let a = UncertainDecimal::from_value_ulps(reading, 7);
+    assert_eq!(a.uncertainty_ulps(), Decimal128::from(7));
+}
+
+#[test]
+fn test_from_value_ulps() {
+    let reading = ud!(12.345).value;
+    assert_eq!(
+        UncertainDecimal::from_value_ulps(reading, 2).to_string(),
+        "12.345 ± 0.002"
+    );
+    assert_eq!(
+        UncertainDecimal::from_value_ulps(reading, 12).to_string(),
+        "12.35 ± 0.01"
+    );
+    assert_eq!(
+        UncertainDecimal::from_value_ulps(ud!(4.7E+3).value, 1).to_string(),
+        "4.7E+3 ± 1E+2"
+    );
+    assert!(UncertainDecimal::from_value_ulps(reading, 0).is_exact());
+}
+
+#[test]
+fn test_align() {
+    let (a, b) = ud!(9.81, 0.02).align(ud!(1.23456, 0.3));
+    assert_eq!(
+        (a.to_string(), b.to_string()),
+        ("9.8 ± 0.1".into(), "1.2 ± 0.3".into())
+    );
+
+    let (a, b) = ud!(12.5, 0.4).align(ud!(0.5));
+    assert_eq!(
+        (a.to_string(), b.to_string()),
+        ("12.5 ± 0.4".into(), "0.5 ± 0".into())
+    );
+
+    let (a, b) = ud!(2.345, 0.005).align(ud!(1.2E+3, 1E+2));
+    assert_eq!(
+        (a.to_string(), b.to_string()),
+        ("0E+2 ± 1E+2".into(), "1.2E+3 ± 1E+2".into())
+    );
+
+    let (a, b) = ud!(1.25).align(ud!(3));
+    assert_eq!(
+        (a.to_string(), b.to_string()),
+        ("1.25 ± 0".into(), "3 ± 0".into())
+    );
+}
+
+#[test]
+fn test_sum_precision_by_place() {
+    let readings = [ud!(1.234, 0.001), ud!(12.1, 0.1), ud!(0.06, 0.01)];
+    let sum: UncertainDecimal = readings.iter().copied().sum();
+    assert_eq!(sum.to_string(), "13.4 ± 0.1");
+    assert_eq!(sum, readings[0] + readings[1] + readings[2]);
+
+    let sum: UncertainDecimal = [ud!(1.52, 0.01), ud!(10)].into_iter().sum();
+    assert_eq!(sum.to_string(), "11.52 ± 0.01");
+    let exact: UncertainDecimal = [ud!(1.234), ud!(12.1), ud!(0.06)].into_iter().sum();
+    assert_eq!(exact.to_string(), "13.394 ± 0");
+
+    let sum: UncertainDecimal = [ud!(1.234, 0.001), ud!(1.2E+2, 1E+1)].into_iter().sum();
+    assert_eq!(sum.to_string(), "1.2E+2 ± 1E+1");
+
+    let empty: UncertainDecimal = std::iter::empty().sum();
+    assert_eq!(empty.to_string(), "0 ± 0");
+}
+
+#[test]
+fn test_to_report_string() {
+    assert_eq!(
+        ud!(9.8132, 0.0234).to_report_string(),
+        "value:       9.81\n\
+         uncertainty: 0.02\n\
+         relative:    ±0.2%\n\
+         interval:    [9.79, 9.83]"
+    );
+}
+
+#[test]
+fn test_weighted_std_dev() {
+    let scattered = weighted_std_dev(&[ud!(10.0, 0.1), ud!(10.4, 0.1)]);
+    assert_eq!(scattered.canonical().to_string(), "10.2 ± 0.2");
+
+    let consistent = weighted_std_dev(&[ud!(10.0, 0.1), ud!(10.05, 0.1)]);
+    assert_eq!(consistent.canonical().to_string(), "10.03 ± 0.07");
+
+    assert_eq!(
+        weighted_std_dev(&[ud!(3.0, 0.2)]).canonical().to_string(),
+        "3.0 ± 0.2"
+    );
+    assert!(weighted_std_dev(&[]).value.is_nan());
+    assert!(weighted_std_dev(&[ud!(1.0, 0.1), ud!(1.0)]).value.is_nan());
+}
+
+#[test]
+fn test_integer_rounding() {
+    let a = ud!(-2.5, 0.03);
+    assert_eq!(a.floor().to_string(), "-3 ± 0.03");
+    assert_eq!(a.ceil().to_string(), "-2 ± 0.03");
+    assert_eq!(a.round().to_string(), "-3 ± 0.03");
+    assert_eq!(a.trunc().to_string(), "-2 ± 0.03");
+    assert_eq!(ud!(7.49, 0.2).round().to_string(), "7 ± 0.2");
+    assert_eq!(ud!(1.2E+3, 1E+2).floor().to_string(), "1200 ± 1E+2");
+}
+
+#[test]
+fn test_parse_glyphs() {
+    assert_eq!(UncertainDecimal::try_parse("−5 ± 0.1"), Ok(ud!(-5, 0.1)));
+    assert_eq!(
+        UncertainDecimal::try_parse("1.2 ± −0.3")
+            .unwrap()
+            .canonical(),
+        ud!(1.2, 0.3)
+    );
+    assert_eq!(
+        UncertainDecimal::try_parse("9.81 +/- 0.02"),
+        Ok(ud!(9.81, 0.02))
+    );
+    assert_eq!(
+        UncertainDecimal::try_parse("9.81+-0.02"),
+        Ok(ud!(9.81, 0.02))
+    );
+    assert_eq!(
+        UncertainDecimal::try_parse("6.674(15)e−11"),
+        UncertainDecimal::try_parse("6.674(15)e-11")
+    );
+    assert!(UncertainDecimal::try_parse("6.674(15)e−11").is_ok());
+    assert_eq!(
+        UncertainDecimal::parse_asymmetric("−1.2 +0.3 −0.1"),
+        UncertainDecimal::parse_asymmetric("-1.2 +0.3 -0.1")
+    );
+}
+
+#[test]
+fn test_with_exponent() {
+    assert_eq!(
+        ud!(9.81, 0.02).with_exponent(-3).to_string(),
+        "9.810 ± 0.020"
+    );
+    assert_eq!(
+        ud!(1234.5, 25).with_exponent(1).to_string(),
+        "1.23E+3 ± 3E+1"
+    );
+    assert_eq!(ud!(9.81, 0.02).with_exponent(0).to_string(), "10 ± 1");
+    assert_eq!(ud!(9.81).with_exponent(-1).to_string(), "9.8 ± 0.0");
+    assert_eq!(
+        ud!(9.81, 0.02).with_exponent(-4).canonical(),
+        ud!(9.81, 0.02)
+    );
+}
+
+#[test]
+fn test_inv_square() {
+    assert_eq!(ud!(2.00, 0.02).inv_square().to_string(), "0.250 ± 0.005");
+    assert_eq!(ud!(-2.00, 0.02).inv_square().to_string(), "0.250 ± 0.005");
+    assert_eq!(ud!(4).inv_square().to_string(), "0.0625 ± 0");
+
+    let ninth = ud!(3).inv_square();
+    assert!(ninth.is_exact());
+    assert_eq!(ninth.value, Decimal128::ONE / Decimal128::from(9));
+
+    let zero = ud!(0.0, 0.1).inv_square();
+    assert!(zero.value.is_infinite() && zero.uncertainty.is_infinite());
+}
+
+#[test]
+fn test_zip_propagate() {
+    let voltage = [ud!(2.00, 0.02), ud!(4.00, 0.04)];
+    let current = [ud!(0.500, 0.005), ud!(0.250, 0.005)];
+
+    let power = zip_propagate(&voltage, &current, UncertainDecimal::mul).unwrap();
+    assert_eq!(power[0].to_string(), "1.00 ± 0.01");
+    assert_eq!(power[1].to_string(), "1.00 ± 0.02");
+    assert_eq!(
+        zip_propagate(&voltage, &current[..1], UncertainDecimal::mul),
+        None
+    );
+    assert_eq!(zip_propagate(&[], &[], UncertainDecimal::add), Some(vec![]));
+}
+
+#[test]
+fn test_empty_aggregates() {
+    let sum: UncertainDecimal = std::iter::empty().sum();
+    assert_eq!(sum, ud!(0));
+    let product: UncertainDecimal = std::iter::empty().product();
+    assert_eq!(product, ud!(1));
+
+    let product: UncertainDecimal = [ud!(2.0, 0.1), ud!(-3)].into_iter().product();
+    assert_eq!(product.to_string(), "-6.0 ± 0.3");
+    let product: UncertainDecimal = [ud!(2.0, 0.1), ud!(0)].into_iter().product();
+    assert_eq!(product.value, Decimal128::ZERO);
+
+    let empty = average(&[]);
+    assert!(empty.value.is_nan() && empty.uncertainty.is_nan());
+    let single = average(&[ud!(1.5).value]);
+    assert_eq!(single.value, ud!(1.5).value);
+    assert!(single.uncertainty.is_nan());
+    assert!(harmonic_mean(&[]).value.is_nan());
+}
+
+#[test]
+fn test_uncertainty_resolution() {
+    assert_eq!(ud!(9.81, 0.02).uncertainty_resolution().to_string(), "0.01");
+    assert_eq!(
+        ud!(1.2E+3, 1E+2).uncertainty_resolution().to_string(),
+        "1E+2"
+    );
+    assert_eq!(ud!(123456789).uncertainty_resolution().to_string(), "1");
+    assert!(ud!(NaN, 0.1).uncertainty_resolution().is_nan());
+}
+
+#[test]
+fn test_add_systematic() {
+    assert_eq!(
+        ud!(9.810, 0.03).add_systematic(ud!(0.04).value).to_string(),
+        "9.81 ± 0.05"
+    );
+    assert_eq!(
+        ud!(9.81).add_systematic(ud!(0.02).value).to_string(),
+        "9.81 ± 0.02"
+    );
+    assert_eq!(
+        ud!(9.81, 0.02).add_systematic(Decimal128::ZERO).to_string(),
+        "9.81 ± 0.02"
+    );
+}
+
+#[test]
+fn test_eval_polynomial() {
+    // 1 + 2x + 3x² at 2.00 ± 0.01: 17 ± 14·0.01
+    let coeffs = [ud!(1).value, ud!(2).value, ud!(3).value];
+    assert_eq!(
+        eval_polynomial(&coeffs, ud!(2.00, 0.01)).to_string(),
+        "17.0 ± 0.1"
+    );
+    assert_eq!(
+        eval_polynomial(&coeffs, ud!(-1.00, 0.01)).to_string(),
+        "2.00 ± 0.04"
+    );
+    assert_eq!(eval_polynomial(&coeffs, ud!(2)).to_string(), "17 ± 0");
+    assert_eq!(eval_polynomial(&[], ud!(2.00, 0.01)).to_string(), "0 ± 0");
+}
+
+#[test]
+fn test_scale() {
+    assert_eq!(
+        ud!(9.81, 0.02).scale(ud!(1000).value).to_string(),
+        "9.81E+3 ± 2E+1"
+    );
+    assert_eq!(
+        ud!(2.54, 0.01).scale(ud!(-0.5).value).to_string(),
+        "-1.270 ± 0.005"
+    );
+    assert_eq!(ud!(3).scale(ud!(2.5).value).to_string(), "7.5 ± 0");
+}
+
+#[test]
+fn test_assert_agrees() {
+    assert!(ud!(10.05, 0.04).agrees_with(&ud!(10.00, 0.03), ud!(1).value));
+    assert!(!ud!(10.06, 0.04).agrees_with(&ud!(10.00, 0.03), ud!(1).value));
+
+    assert_agrees!(ud!(10.05, 0.04), ud!(10.00, 0.03));
+    assert_agrees!(ud!(10.10, 0.04), ud!(10.00, 0.03), ud!(2).value);
+}
+
+#[test]
+#[should_panic(expected = "diff: 4σ (tolerance 1σ)")]
+fn test_assert_agrees_message() {
+    assert_agrees!(ud!(10.20, 0.04), ud!(10.00, 0.03));
+}
+
+#[test]
+fn test_average_with_uncertainties() {
+    let wide_bars = [ud!(9.80, 0.05), ud!(9.82, 0.05), ud!(9.81, 0.05)];
+    assert_eq!(
+        average_with_uncertainties(&wide_bars)
+            .canonical()
+            .to_string(),
+        "9.81 ± 0.05"
+    );
+
+    let scattered = [ud!(9.6, 0.01), ud!(10.0, 0.01), ud!(9.8, 0.01)];
+    assert_eq!(
+        average_with_uncertainties(&scattered),
+        average(&[ud!(9.6).value, ud!(10.0).value, ud!(9.8).value])
+    );
+
+    assert_eq!(
+        average_with_uncertainties(&[ud!(3.0, 0.2)])
+            .canonical()
+            .to_string(),
+        "3.0 ± 0.2"
+    );
+    assert!(average_with_uncertainties(&[]).value.is_nan());
+}
+
+#[test]
+fn test_constants() {
+    assert_eq!(
+        decimal::PI.to_string(),
+        "3.141592653589793238462643383279503"
+    );
+    assert_eq!(
+        decimal::E.to_string(),
+        "2.718281828459045235360287471352662"
+    );
+    assert_eq!(decimal::E, decimal::exp(Decimal128::ONE));
+    assert_eq!(decimal::PI.digits(), 34);
+
+    assert!(UncertainDecimal::PI.is_exact());
+    assert_eq!(UncertainDecimal::E.value, decimal::E);
+    assert_eq!(
+        (UncertainDecimal::PI * ud!(2.00, 0.01)).to_string(),
+        "6.28 ± 0.03"
+    );
+
+    // An exact factor keeps the constant's full precision.
+    let tau = UncertainDecimal::PI * ud!(2);
+    assert!(tau.is_exact());
+    assert_eq!(tau.value, decimal::PI * Decimal128::from(2));
+    assert_eq!(tau.value.digits(), 34);
+}
+
+#[test]
+fn test_parse_overflow() {
+    use ParseUncertainDecimalError::*;
+
+    let max = "1.234567890123456789012345678901234";
+    let over = "1.2345678901234567890123456789012345";
+    assert!(UncertainDecimal::try_parse(&format!("{} ± 0.1", max)).is_ok());
+    assert!(UncertainDecimal::try_parse("0.000001234567890123456789012345678901234 ± 0.1").is_ok());
+    assert_eq!(
+        UncertainDecimal::try_parse(&format!("{} ± 0.1", over)),
+        Err(Overflow)
+    );
+    assert_eq!(
+        UncertainDecimal::try_parse(&format!("1.5 ± {}", over)),
+        Err(Overflow)
+    );
+    assert_eq!(
+        UncertainDecimal::try_parse(&format!("{}(12)e-3", over)),
+        Err(Overflow)
+    );
+    assert_eq!(
+        UncertainDecimal::try_parse(&format!("{} +0.1 -0.2", over)),
+        Err(Overflow)
+    );
+    for input in [
+        "1e7000 ± 1",
+        "1 ± 1e7000",
+        "1e-7000 ± 1",
+        "1(1)e7000",
+        "1(1)e2147483647",
+        "1.5(1)e-6176",
+        "1e7000 +0.1 -0.2",
+    ] {
+        assert_eq!(
+            UncertainDecimal::try_parse(input),
+            Err(Overflow),
+            "{}",
+            input
+        );
+    }
+    assert!(UncertainDecimal::try_parse("1(1)e6000").is_ok());
+    assert!(UncertainDecimal::try_parse("Infinity ± 1").is_ok());
+    assert_eq!(Overflow.to_string(), "number out of range");
+}
+
+#[test]
+fn test_partition_significant() {
+    let candidates = [
+        ud!(12.0, 0.5),
+        ud!(10.4, 0.5),
+        ud!(13.0, 0.2),
+        ud!(NaN, 0.1),
+    ];
+
+    let (significant, rest) = partition_significant(&candidates, ud!(10).value, ud!(3).value);
+    assert_eq!(significant, [candidates[0], candidates[2]]);
+    assert_eq!(rest.len(), 2);
+    assert_eq!(rest[0], candidates[1]);
+}
+
+#[test]
+fn test_to_string_compact() {
+    assert_eq!(ud!(42).to_string_compact(), "42");
+    assert_eq!(ud!(42, 0.0).to_string_compact(), "42");
+    assert_eq!(ud!(9.81, 0.02).to_string_compact(), "9.81 ± 0.02");
+    assert_eq!(ud!(42).to_string(), "42 ± 0");
+}
+
+#[test]
+fn test_histogram() {
+    let counts = [
+        ud!(0.5, 0.1),
+        ud!(1.0, 0.3),
+        ud!(1.5, 0.4),
+        ud!(2.0, 0.1),
+        ud!(2.5, 0.1),
+    ];
+    let edges = [ud!(0).value, ud!(1).value, ud!(2).value];
+
+    let bins = histogram(&counts, &edges);
+    assert_eq!(bins.len(), 2);
+    assert_eq!(bins[0].to_string(), "0.5 ± 0.1");
+    assert_eq!(bins[1].to_string(), "4.5 ± 0.5");
+
+    assert_eq!(histogram(&counts, &edges[..2])[0].to_string(), "1.5 ± 0.3");
+    assert_eq!(histogram(&[], &edges)[0].to_string(), "0 ± 0");
+    assert!(histogram(&counts, &edges[..1]).is_empty());
+}
+
+#[test]
+fn test_efficiency() {
+    let passed = ud!(90, 9.5);
+    let total = ud!(100, 10);
+
+    assert_eq!(
+        efficiency(passed, total, EfficiencyMode::Independent).to_string(),
+        "9E+1 ± 1E+1"
+    );
+    assert_eq!(
+        efficiency(passed, total, EfficiencyMode::Binomial).to_string(),
+        "90 ± 3"
+    );
+    assert_eq!(
+        efficiency(ud!(100, 10), total, EfficiencyMode::Binomial).to_string(),
+        "100 ± 0"
+    );
+    assert!(efficiency(passed, ud!(0), EfficiencyMode::Binomial)
+        .value
+        .is_nan());
+}
+
+#[test]
+fn test_by_significance() {
+    let candidates = [
+        ud!(100, 50),
+        ud!(3.0, 0.5),
+        ud!(6.0, 1.0),
+        ud!(0.1, 1.0),
+        ud!(NaN, 1),
+    ];
+
+    assert_eq!(
+        max_by_significance(&candidates, Decimal128::ZERO),
+        Some(candidates[1])
+    );
+    assert_eq!(
+        min_by_significance(&candidates, Decimal128::ZERO),
+        Some(candidates[3])
+    );
+    assert_eq!(
+        min_by_significance(&candidates, ud!(100).value),
+        Some(candidates[0])
+    );
+    assert_eq!(max_by_significance(&[], Decimal128::ZERO), None);
+    assert_eq!(
+        max_by_significance(&candidates[4..], Decimal128::ZERO),
+        None
+    );
+}
+
+#[test]
+fn test_to_engineering_string() {
+    assert_eq!(ud!(12345, 500).to_engineering_string(), "12.3e3 ± 0.5e3");
+    assert_eq!(ud!(12345, 500).to_si_string(), "12.3k ± 0.5k");
+    assert_eq!(ud!(0.004712, 0.00002).to_si_string(), "4.71m ± 0.02m");
+    assert_eq!(ud!(1.5E+5, 1E+4).to_si_string(), "150k ± 10k");
+    assert_eq!(ud!(9.81, 0.02).to_engineering_string(), "9.81 ± 0.02");
+    assert_eq!(ud!(9.81, 0.02).to_si_string(), "9.81 ± 0.02");
+    assert_eq!(
+        ud!(-3.3E-7, 2E-8).to_engineering_string(),
+        "-330e-9 ± 20e-9"
+    );
+    assert_eq!(ud!(0.000, 0.003).to_si_string(), "0m ± 3m");
+    assert_eq!(ud!(4.2E+30, 1E+29).to_si_string(), "4.2e30 ± 0.1e30");
+    assert_eq!(ud!(1500).to_si_string(), "1.500k ± 0");
+    assert_eq!(ud!(1500).to_engineering_string(), "1.500e3 ± 0");
+    assert_eq!(ud!(1.5).to_si_string(), "1.5 ± 0");
+}
+
+#[test]
+fn test_clamp_nonnegative() {
+    assert_eq!(
+        ud!(0.3, 0.5).clamp_nonnegative_asymmetric(),
+        (ud!(0.3).value, ud!(0.5).value, ud!(0.3).value)
+    );
+    assert_eq!(
+        ud!(0.8, 0.5).clamp_nonnegative_asymmetric(),
+        (ud!(0.8).value, ud!(0.5).value, ud!(0.5).value)
+    );
+    assert_eq!(
+        ud!(-0.3, 0.5).clamp_nonnegative_asymmetric(),
+        (ud!(-0.3).value, ud!(0.5).value, ud!(0.5).value)
+    );
+    assert_eq!(
+        ud!(0.0, 0.5).clamp_nonnegative_asymmetric(),
+        (ud!(0.0).value, ud!(0.5).value, ud!(0.5).value)
+    );
+}
+
+#[test]
+fn test_from_iterator() {
+    let readings = [
+        ud!(9.79).value,
+        ud!(9.82).value,
+        ud!(9.81).value,
+        ud!(9.80).value,
+    ];
+
+    let m: UncertainDecimal = readings.into_iter().collect();
+    assert_eq!(m, average(&readings));
+    assert_eq!(m.canonical().to_string(), "9.81 ± 0.01");
+
+    let empty: UncertainDecimal = std::iter::empty().collect();
+    assert!(empty.value.is_nan());
+    let single: UncertainDecimal = std::iter::once(readings[0]).collect();
+    assert!(single.uncertainty.is_nan());
+}
+
+#[test]
+fn test_exp_decay() {
+    // One time constant: A/e, with relative uncertainties 1% from A and 2% from τ
+    let decayed = exp_decay(ud!(100.0, 1.0), ud!(5).value, ud!(5.0, 0.1));
+    assert_eq!(decayed.to_string(), "36.8 ± 0.8");
+
+    assert_eq!(
+        exp_decay(ud!(100.0, 1.0), Decimal128::ZERO, ud!(5.0, 0.1)).to_string(),
+        "100 ± 1"
+    );
+    assert!(exp_decay(ud!(100.0, 1.0), ud!(5).value, ud!(0))
+        .value
+        .is_nan());
+}
+
+#[test]
+fn test_try_into_exact() {
+    let tolerance = ud!(0.001).value;
+
+    assert_eq!(
+        ud!(9.81, 0.0001).try_into_exact(tolerance),
+        Some(ud!(9.81).value)
+    );
+    assert_eq!(ud!(9.81, 0.001).try_into_exact(tolerance), None);
+    assert_eq!(
+        ud!(9.81).try_into_exact(Decimal128::ZERO),
+        Some(ud!(9.81).value)
+    );
+    assert_eq!(ud!(9.81, NaN).try_into_exact(tolerance), None);
+}
+
+#[test]
+fn test_interpolate() {
+    let xs = [ud!(0).value, ud!(10).value, ud!(20).value];
+    let ys = [ud!(1.00, 0.03), ud!(2.00, 0.04), ud!(2.50, 0.04)];
+
+    // Halfway through the first segment: ½·0.03 and ½·0.04 from the table,
+    // and 0.1·0.2 from x.
+    assert_eq!(
+        interpolate(ud!(5.0, 0.2), &xs, &ys).to_string(),
+        "1.50 ± 0.03"
+    );
+    assert_eq!(interpolate(ud!(15), &xs, &ys).to_string(), "2.25 ± 0.03");
+    assert_eq!(interpolate(ud!(10), &xs, &ys).to_string(), "2.00 ± 0.04");
+
+    assert!(interpolate(ud!(25), &xs, &ys).value.is_nan());
+    assert!(interpolate(ud!(-1), &xs, &ys).value.is_nan());
+    assert!(interpolate(ud!(5), &xs, &ys[..2]).value.is_nan());
+    assert!(interpolate(ud!(0), &xs[..1], &ys[..1]).value.is_nan());
+}
+
+#[test]
+fn test_canonical_uncertainty_dominated() {
+    let cases = [
+        (ud!(0.3, 5), "0 ± 5"),
+        (ud!(-0.3, 5), "0 ± 5"),
+        (ud!(-0.7, 2), "-1 ± 2"),
+        (ud!(0.5, 1), "1 ± 1"),
+        (ud!(-0.5, 1), "-1 ± 1"),
+        (ud!(0.0003, 0.05), "0.00 ± 0.05"),
+        (ud!(0.00, 0.5), "0.0 ± 0.5"),
+        (ud!(0.123456789, 0.9), "0.1 ± 0.9"),
+        (ud!(9.6, 40), "1E+1 ± 4E+1"),
+        (ud!(0.45, 30), "0E+1 ± 3E+1"),
+        (ud!(123, 4567), "0E+3 ± 5E+3"),
+    ];
+
+    for (m, expected) in cases {
+        let canonical = m.canonical();
+        assert_eq!(canonical.to_string(), expected, "canonical form of {}", m);
+        assert_eq!(canonical.value.exponent(), canonical.uncertainty.exponent());
+        assert!(!canonical.value.is_negative() || !canonical.value.is_zero());
+    }
+}
+
+#[test]
+fn test_cluster_consistent() {
+    let results = [
+        ud!(10.0, 0.1),
+        ud!(12.0, 0.1),
+        ud!(10.3, 0.1),
+        ud!(10.15, 0.1),
+        ud!(12.1, 0.1),
+        ud!(20.0, 0.1),
+    ];
+
+    // 10.0 and 10.3 disagree, but both agree with 10.15.
+    assert_eq!(
+        cluster_consistent(&results, ud!(2).value),
+        [vec![0, 2, 3], vec![1, 4], vec![5]]
+    );
+    assert_eq!(cluster_consistent(&results, ud!(0).value).len(), 6);
+    assert!(cluster_consistent(&[], ud!(2).value).is_empty());
+}
+
+#[test]
+fn test_ratio_estimators() {
+    let num = [ud!(10.0, 0.3), ud!(2.0, 0.4)];
+    let den = [ud!(100.0, 1.0), ud!(4.0, 1.0)];
+
+    // Ratios 0.1 and 0.5; the second pair's noisy denominator dominates.
+    assert_eq!(mean_of_ratios(&num, &den).to_string(), "0.30 ± 0.08");
+    // 12/104, pulled towards the better-measured first pair.
+    assert_eq!(ratio_of_means(&num, &den).to_string(), "0.115 ± 0.005");
+
+    assert_eq!(
+        ratio_of_means(&[ud!(1.0, 0.1)], &[ud!(2.0, 0.1)]).to_string(),
+        "0.50 ± 0.06"
+    );
+    assert!(mean_of_ratios(&num, &den[..1]).value.is_nan());
+    assert!(ratio_of_means(&[], &[]).value.is_nan());
+}
+
+#[test]
+fn test_parallel_matches_serial() {
+    let a: Vec<UncertainDecimal> = (1..=200)
+        .map(|i| UncertainDecimal::from_value_ulps(Decimal128::from(i) / Decimal128::from(7), 3))
+        .collect();
+    let b: Vec<UncertainDecimal> = a.iter().rev().copied().collect();
+    let combine = |a: &[UncertainDecimal], b: &[UncertainDecimal]| {
+        zip_propagate(a, b, |x, y| x * y + x / y - y).unwrap()
+    };
+
+    let serial = combine(&a, &b);
+    let parallel: Vec<UncertainDecimal> = std::thread::scope(|scope| {
+        let workers: Vec<_> = a
+            .chunks(25)
+            .zip(b.chunks(25))
+            .map(|(a, b)| scope.spawn(move || combine(a, b)))
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+
+    assert_eq!(serial.len(), parallel.len());
+    for (s, p) in serial.iter().zip(&parallel) {
+        assert_eq!(format!("{:?}", s), format!("{:?}", p));
+    }
+}
+
+#[test]
+fn test_from_confidence_interval() {
+    let k2 =
+        UncertainDecimal::from_confidence_interval(ud!(9.81).value, ud!(0.1).value, ud!(2).value);
+    assert_eq!(k2.to_string(), "9.81 ± 0.05");
+    assert_eq!(k2.upper_bound(), ud!(9.86).value);
+
+    let k1 = UncertainDecimal::from_confidence_interval(
+        ud!(9.81).value,
+        ud!(0.1).value,
+        Decimal128::ONE,
+    );
+    assert_eq!(k1, ud!(9.81, 0.1));
+}
+
+#[test]
+fn test_value_to_significant_string() {
+    assert_eq!(ud!(9.8123, 0.02).value_to_significant_string(), "9.81");
+    assert_eq!(
+        ud!(1.2345E-6, 2E-8).value_to_significant_string(),
+        "0.00000123"
+    );
+    assert_eq!(ud!(1234, 150).value_to_significant_string(), "1.2E+3");
+    assert_eq!(ud!(1234, 5).value_to_significant_string(), "1234");
+    assert_eq!(ud!(-0.3, 5).value_to_significant_string(), "0");
+    assert_eq!(ud!(12.50).value_to_significant_string(), "12.50");
+    assert_eq!(ud!(NaN, 1).value_to_significant_string(), "NaN");
 }
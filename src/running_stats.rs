@@ -0,0 +1,76 @@
+use dec::Decimal128;
+
+use crate::{decimal, UncertainDecimal};
+
+/// Streaming mean and sample standard deviation, using Welford's online
+/// algorithm so the readings never need to be stored.
+///
+/// [`RunningStats::result`] matches [`crate::average`] over the same readings.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunningStats {
+    count: u64,
+    mean: Decimal128,
+    m2: Decimal128,
+}
+
+impl RunningStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, d: Decimal128) {
+        self.count += 1;
+        let delta = d - self.mean;
+        self.mean += delta / Decimal128::from(self.count);
+        self.m2 += delta * (d - self.mean);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The mean ± sample standard deviation so far.
+    ///
+    /// The value is NaN with no readings, and the uncertainty is NaN with
+    /// fewer than two.
+    pub fn result(&self) -> UncertainDecimal {
+        if self.count == 0 {
+            return UncertainDecimal::NAN;
+        }
+
+        UncertainDecimal {
+            value: self.mean,
+            uncertainty: decimal::sqrt(self.m2 / Decimal128::from(self.count - 1)),
+        }
+    }
+}
+
+impl Extend<Decimal128> for RunningStats {
+    fn extend<I: IntoIterator<Item = Decimal128>>(&mut self, iter: I) {
+        for d in iter {
+            self.push(d);
+        }
+    }
+}
+
+#[test]
+fn test_running_stats() {
+    let readings = [
+        crate::ud!(9.79).value,
+        crate::ud!(9.82).value,
+        crate::ud!(9.81).value,
+        crate::ud!(9.80).value,
+    ];
+
+    let mut stats = RunningStats::new();
+    stats.extend(readings);
+    assert_eq!(stats.count(), 4);
+    assert_eq!(stats.result(), crate::average(&readings));
+    assert_eq!(stats.result().canonical().to_string(), "9.81 ± 0.01");
+
+    let mut stats = RunningStats::new();
+    assert!(stats.result().value.is_nan());
+    stats.push(crate::ud!(1.5).value);
+    assert_eq!(stats.result().value.to_string(), "1.5");
+    assert!(stats.result().uncertainty.is_nan());
+}
@@ -0,0 +1,219 @@
+use std::{
+    cmp::{max, min},
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use dec::{Context, Decimal128, Rounding};
+
+use crate::{decimal, UncertainDecimal};
+
+/// A measurement carrying separate statistical and systematic uncertainties.
+///
+/// Statistical parts combine in quadrature, like [`UncertainDecimal`].
+/// Systematic parts add linearly, scaled by each operand's sensitivity, so
+/// they never average down. They add in differences too, which is a
+/// conservative upper bound: errors fully correlated between the operands
+/// would partly cancel there.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UncertainDecimalWithSystematic {
+    pub value: Decimal128,
+    pub statistical: Decimal128,
+    pub systematic: Decimal128,
+}
+
+impl Display for UncertainDecimalWithSystematic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ± {} ± {}",
+            self.value, self.statistical, self.systematic
+        )
+    }
+}
+
+impl UncertainDecimalWithSystematic {
+    /// Combines both uncertainties in quadrature.
+    pub fn total(&self) -> UncertainDecimal {
+        UncertainDecimal {
+            value: self.value,
+            uncertainty: decimal::sqrt(
+                self.statistical * self.statistical + self.systematic * self.systematic,
+            ),
+        }
+        .canonical()
+    }
+
+    /// Rounds each uncertainty to one significant figure, and the value and
+    /// the coarser uncertainty to the last place of the finer one.
+    pub fn canonical(mut self) -> Self {
+        let parts = [self.statistical, self.systematic].map(|uncertainty| {
+            UncertainDecimal {
+                value: self.value,
+                uncertainty,
+            }
+            .canonical()
+        });
+        let finest = parts
+            .iter()
+            .filter(|part| !part.is_exact())
+            .min_by_key(|part| part.uncertainty.exponent());
+        let finest = match finest {
+            Some(finest) => *finest,
+            None => return self,
+        };
+
+        let mut ctx = Context::<Decimal128>::default();
+        ctx.set_rounding(Rounding::HalfUp);
+        self.value = finest.value;
+        [self.statistical, self.systematic] =
+            parts.map(|part| ctx.quantize(part.uncertainty, finest.uncertainty));
+
+        self
+    }
+
+    // Combines both parts of `self` and `rhs` given the magnitude of the
+    // result's derivative with respect to each value. Working with absolute
+    // rather than relative uncertainties keeps zero-valued operands finite.
+    fn combine(
+        &self,
+        rhs: &Self,
+        d_self: Decimal128,
+        d_rhs: Decimal128,
+    ) -> (Decimal128, Decimal128) {
+        let (stat_l, stat_r) = (d_self * self.statistical, d_rhs * rhs.statistical);
+
+        (
+            decimal::sqrt(stat_l * stat_l + stat_r * stat_r),
+            d_self * self.systematic + d_rhs * rhs.systematic,
+        )
+    }
+}
+
+impl From<UncertainDecimal> for UncertainDecimalWithSystematic {
+    fn from(ud: UncertainDecimal) -> Self {
+        UncertainDecimalWithSystematic {
+            value: ud.value,
+            statistical: ud.uncertainty,
+            systematic: Decimal128::ZERO,
+        }
+    }
+}
+
+impl Add for UncertainDecimalWithSystematic {
+    type Output = UncertainDecimalWithSystematic;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        UncertainDecimalWithSystematic {
+            value: decimal::with_exponent(
+                self.value + rhs.value,
+                max(self.value.exponent(), rhs.value.exponent()),
+            ),
+            statistical: decimal::sqrt(
+                self.statistical * self.statistical + rhs.statistical * rhs.statistical,
+            ),
+            systematic: self.systematic + rhs.systematic,
+        }
+        .canonical()
+    }
+}
+
+impl Div for UncertainDecimalWithSystematic {
+    type Output = UncertainDecimalWithSystematic;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let value = self.value / rhs.value;
+        let d_self = decimal::abs(Decimal128::ONE / rhs.value);
+        let (statistical, systematic) = self.combine(&rhs, d_self, decimal::abs(value) * d_self);
+        // A zero quotient has no significant digits to keep, and dividing
+        // leaves it at a coarse exponent, so it is rounded by the
+        // uncertainties instead.
+        let value = if value.is_zero() {
+            decimal::with_exponent(value, min(statistical.exponent(), systematic.exponent()))
+        } else {
+            decimal::with_digits(value, min(self.value.digits(), rhs.value.digits()))
+        };
+
+        UncertainDecimalWithSystematic {
+            value,
+            statistical,
+            systematic,
+        }
+        .canonical()
+    }
+}
+
+impl Mul for UncertainDecimalWithSystematic {
+    type Output = UncertainDecimalWithSystematic;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let value = self.value * rhs.value;
+        let (statistical, systematic) =
+            self.combine(&rhs, decimal::abs(rhs.value), decimal::abs(self.value));
+
+        UncertainDecimalWithSystematic {
+            value: decimal::with_digits(value, min(self.value.digits(), rhs.value.digits())),
+            statistical,
+            systematic,
+        }
+        .canonical()
+    }
+}
+
+impl Neg for UncertainDecimalWithSystematic {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            value: -self.value,
+            ..self
+        }
+    }
+}
+
+impl Sub for UncertainDecimalWithSystematic {
+    type Output = UncertainDecimalWithSystematic;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+#[test]
+fn test_systematic() {
+    let a = UncertainDecimalWithSystematic {
+        value: crate::ud!(10.00).value,
+        statistical: crate::ud!(0.03).value,
+        systematic: crate::ud!(0.05).value,
+    };
+    let b = UncertainDecimalWithSystematic {
+        value: crate::ud!(5.000).value,
+        statistical: crate::ud!(0.04).value,
+        systematic: crate::ud!(0.05).value,
+    };
+
+    assert_eq!((a + b).to_string(), "15.00 ± 0.05 ± 0.10");
+    assert_eq!((a - b).to_string(), "5.00 ± 0.05 ± 0.10");
+    assert_eq!((a * b).to_string(), "50.0 ± 0.4 ± 0.8");
+    assert_eq!((a / b).to_string(), "2.00 ± 0.02 ± 0.03");
+    assert_eq!(a.total().to_string(), "10.00 ± 0.06");
+}
+
+#[test]
+fn test_systematic_zero_value() {
+    let zero = UncertainDecimalWithSystematic {
+        value: crate::ud!(0.0).value,
+        statistical: crate::ud!(0.1).value,
+        systematic: crate::ud!(0.2).value,
+    };
+    let b = UncertainDecimalWithSystematic {
+        value: crate::ud!(5.000).value,
+        statistical: crate::ud!(0.04).value,
+        systematic: crate::ud!(0.05).value,
+    };
+
+    assert_eq!((zero * b).to_string(), "0.0 ± 0.5 ± 1.0");
+    assert_eq!((b * zero).to_string(), "0.0 ± 0.5 ± 1.0");
+    assert_eq!((zero / b).to_string(), "0.00 ± 0.02 ± 0.04");
+    assert!((b / zero).statistical.is_infinite());
+}